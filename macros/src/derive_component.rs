@@ -2,116 +2,141 @@ use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
 use quote::{format_ident, quote, quote_spanned};
 use syn::parse::ParseStream;
-use syn::{parse_macro_input, DeriveInput, Error, Type};
-use syn::{Data, DataStruct, Field, Fields, Generics};
+use syn::Type;
+use syn::{Attribute, Data, DataEnum, DataStruct, Field, Fields, Generics, Visibility};
 use syn::{Result, Token};
 
 pub(crate) fn derive_inject_impl(
     ident: Ident,
+    vis: Visibility,
     data: Data,
     mut generics: Generics,
+    attrs: Vec<Attribute>,
 ) -> syn::Result<TokenStream> {
-    let fields: Vec<_> = match &data {
-        Data::Struct(DataStruct { fields, .. }) => match fields {
-            Fields::Named(named) => named.named.clone(),
-            Fields::Unit => Default::default(),
-            _ => {
-                return Ok(quote_spanned! {
-                    ident.span() => compile_error!("you can only derive Inject on structs with named fields or empty structs");
-                });
-            }
-        },
+    let fields = match &data {
+        Data::Struct(DataStruct { fields, .. }) => fields,
+        Data::Enum(data_enum) => {
+            return derive_enum_impl(ident, vis, data_enum, generics, attrs);
+        }
         _ => {
             return Ok(quote_spanned! {
-                ident.span() => compile_error!("you can only derive Inject on structs");
+                ident.span() => compile_error!("you can only derive Inject on structs and enums");
             });
         }
-    }.into_iter()
+    };
+
+    let fields_with_types_and_settings = read_fields(fields)?;
+
+    let fields_init: Vec<_> = fields_with_types_and_settings
+        .iter()
+        .map(|(field_name, field_type, injection)| field_init(field_name, field_type, injection))
         .collect();
 
-    let fields_with_types_and_settings: Vec<(TokenStream, Type, DefaultValue)> = fields
+    let fields_types: Vec<_> = fields_with_types_and_settings
         .iter()
-        .map(|field| {
-            let ident = format_ident!("{}", field.ident.as_ref().unwrap());
-            let name = quote!( #ident );
-            let typed_name = {
-                let field_type = field.ty.clone();
-                field_type
-            };
-            let default_value = read_default_value(field)?;
-            Ok((name, typed_name, default_value))
+        .filter_map(|(_, field_type, injection)| match injection {
+            FieldInjection::Inject => Some(field_type.to_token_stream()),
+            FieldInjection::Tagged(tag) => Some(tagged_type(field_type, tag)),
+            _ => None,
         })
-        .collect::<syn::Result<Vec<_>>>()?;
+        .collect();
 
-    let defaults: Vec<_> = fields_with_types_and_settings
+    let optional_types: Vec<_> = fields_with_types_and_settings
         .iter()
-        .filter(|(_, _, value)| !matches!(value, DefaultValue::None))
-        .filter_map(|(filed_name, _, value)| match value {
-            DefaultValue::DefaultFunction(func) => Some(quote!(#filed_name: #func())),
-            DefaultValue::Default => Some(quote!(#filed_name: Default::default())),
+        .filter_map(|(_, field_type, injection)| match injection {
+            FieldInjection::Optional(inner_type) => Some(inner_type),
+            FieldInjection::DefaultIfMissing(_) => Some(field_type),
             _ => None,
         })
         .collect();
 
-    let (inject_field, fields_types): (Vec<_>, Vec<_>) = fields_with_types_and_settings
-        .into_iter()
-        .filter_map(|(field, field_type, default_status)| {
-            if let DefaultValue::None = default_status {
-                return Some((field, field_type));
-            }
-            None
+    let default_functions: Vec<_> = fields_with_types_and_settings
+        .iter()
+        .filter_map(|(_, _, injection)| match injection {
+            FieldInjection::DefaultWith(func) => Some(func),
+            _ => None,
         })
-        .unzip();
+        .collect();
 
-    let defaults = {
-        if defaults.is_empty() {
-            quote!()
-        } else if inject_field.is_empty() {
-            quote!(#(#defaults),*)
-        } else {
-            quote!(,#(#defaults),*)
-        }
-    };
+    let named_types: Vec<_> = fields_with_types_and_settings
+        .iter()
+        .filter_map(|(_, field_type, injection)| match injection {
+            FieldInjection::Named(name) => Some((name, field_type)),
+            _ => None,
+        })
+        .collect();
+    let named_names = named_types.iter().map(|(name, _)| name);
+    let named_types = named_types.iter().map(|(_, field_type)| field_type);
 
-    // Добавление требования реализации трейта Clone для каждого дженерика
-    for param in generics.params.iter_mut() {
-        use syn::{GenericParam, TypeParamBound};
-        if let GenericParam::Type(type_param) = param {
-            type_param
-                .bounds
-                .push(TypeParamBound::Trait(syn::parse_quote!(Clone)));
-        }
-    }
+    // dependencies as nested pairs, for example (A, (B, ()))
+    let default_types: Vec<_> = default_functions
+        .iter()
+        .flat_map(|func| closure_argument_types(func))
+        .collect();
+    let static_dependencies = fields_types
+        .iter()
+        .chain(default_types.iter())
+        .rev()
+        .fold(quote!(()), |tail, head| quote!((#head, #tail)));
+
+    add_clone_bounds(&mut generics);
+    let debug_line = debug_line_fn();
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let attributes = read_component_attributes(&attrs)?;
+    if let Some(select) = &attributes.select {
+        return Err(syn::Error::new_spanned(
+            select,
+            "#[component(select = ...)] can only be used with enums.",
+        ));
+    }
+    let lifecycle_impl = lifecycle_impl(&ident, &generics, &attributes);
+    let lifecycle_hooks = lifecycle_hooks_fn(&attributes);
+
     Ok(quote!(
+        #lifecycle_impl
+
+        #[automatically_derived]
+        impl #impl_generics mydi::component_meta::StaticDependencies for #ident #ty_generics #where_clause {
+            type Dependencies = #static_dependencies;
+        }
 
         #[automatically_derived]
         impl #impl_generics mydi::component_meta::ComponentMeta for #ident #ty_generics #where_clause {
             fn inject(injector: &mydi::injector::Injector) -> anyhow::Result<Self>  {
-                #(let #inject_field = injector.get()?);*;
                 let result = Self {
-                    #(#inject_field),*
-                    #defaults
+                    #(#fields_init),*
                 };
                 Ok(result)
             }
 
-            fn debug_line() -> Option<String> {
-                let line_num = line!();
-                let file_name = file!();
-                let mut result = String::new();
-                result.push_str(file_name);
-                result.push_str(":");
-                result.push_str(line_num.to_string().as_str());
-                Some(result)
-            }
+            #debug_line
+
+            #lifecycle_hooks
 
             fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
                 use std::any::TypeId;
                 use std::any::type_name;
-                vec! [
+                let result = vec! [
                     #( (TypeId::of::<#fields_types>(), type_name::<#fields_types>()) ),*
+                ];
+                #( let result = [result, mydi::injected_fn::dependencies_names(&(#default_functions))].concat(); )*
+                result
+            }
+
+            fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+                use std::any::TypeId;
+                use std::any::type_name;
+                vec! [
+                    #( (TypeId::of::<#optional_types>(), type_name::<#optional_types>()) ),*
+                ]
+            }
+
+            fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+                use std::any::TypeId;
+                use std::any::type_name;
+                vec! [
+                    #( (#named_names, TypeId::of::<#named_types>(), type_name::<#named_types>()) ),*
                 ]
             }
 
@@ -120,43 +145,372 @@ pub(crate) fn derive_inject_impl(
     ))
 }
 
-#[derive(Debug)]
-enum DefaultValue {
-    None,
+// Enums are built by the variant returned from the selector function.
+// Only fields of the selected variant are constructed, while `verify` checks dependencies of all variants,
+// so the wiring doesn't depend on the configuration.
+fn derive_enum_impl(
+    ident: Ident,
+    vis: Visibility,
+    data_enum: &DataEnum,
+    mut generics: Generics,
+    attrs: Vec<Attribute>,
+) -> Result<TokenStream> {
+    let attributes = read_component_attributes(&attrs)?;
+    let Some(select) = attributes.select.clone() else {
+        return Err(syn::Error::new_spanned(
+            ident,
+            "Expected #[component(select = function)] on the enum.",
+        ));
+    };
+    let variant_enum = format_ident!("{}Variant", ident);
+
+    let mut variants = Vec::new();
+    let mut match_arms = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut optional_dependencies = Vec::new();
+    let mut named_dependencies = Vec::new();
+    for variant in data_enum.variants.iter() {
+        let variant_ident = &variant.ident;
+        let fields = read_fields(&variant.fields)?;
+        let fields_init = fields.iter().map(|(field_name, field_type, injection)| match injection {
+            FieldInjection::Inject => {
+                let field = variant_field(field_type);
+                quote!(#field_name: #field.inject(injector)?)
+            }
+            other => field_init(field_name, field_type, other),
+        });
+        for (_, field_type, injection) in fields.iter() {
+            match injection {
+                FieldInjection::Inject => {
+                    let field = variant_field(field_type);
+                    dependencies.push(quote!(result.extend(#field.dependencies_names());));
+                    optional_dependencies.push(quote!(result.extend(#field.optional_dependencies_names());));
+                    named_dependencies.push(quote!(result.extend(#field.named_dependencies_names());));
+                }
+                FieldInjection::Optional(inner_type) => optional_dependencies.push(quote!(
+                    result.push((std::any::TypeId::of::<#inner_type>(), std::any::type_name::<#inner_type>()));
+                )),
+                FieldInjection::DefaultIfMissing(_) => optional_dependencies.push(quote!(
+                    result.push((std::any::TypeId::of::<#field_type>(), std::any::type_name::<#field_type>()));
+                )),
+                FieldInjection::Tagged(tag) => {
+                    let tagged = tagged_type(field_type, tag);
+                    dependencies.push(quote!(
+                        result.push((std::any::TypeId::of::<#tagged>(), std::any::type_name::<#tagged>()));
+                    ))
+                }
+                FieldInjection::DefaultWith(func) => dependencies.push(quote!(
+                    result.extend(mydi::injected_fn::dependencies_names(&(#func)));
+                )),
+                FieldInjection::Named(name) => named_dependencies.push(quote!(
+                    result.push((#name, std::any::TypeId::of::<#field_type>(), std::any::type_name::<#field_type>()));
+                )),
+                _ => {}
+            }
+        }
+
+        match_arms.push(quote!(
+            #variant_enum::#variant_ident => Self::#variant_ident { #(#fields_init),* }
+        ));
+        variants.push(variant_ident);
+    }
+
+    add_clone_bounds(&mut generics);
+    let debug_line = debug_line_fn();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let lifecycle_impl = lifecycle_impl(&ident, &generics, &attributes);
+    let lifecycle_hooks = lifecycle_hooks_fn(&attributes);
+
+    Ok(quote!(
+        #lifecycle_impl
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #vis enum #variant_enum {
+            #(#variants),*
+        }
+
+        // dependencies of the selector and the variants are checked only by `verify`
+        #[automatically_derived]
+        impl #impl_generics mydi::component_meta::StaticDependencies for #ident #ty_generics #where_clause {
+            type Dependencies = ();
+        }
+
+        #[automatically_derived]
+        impl #impl_generics mydi::component_meta::ComponentMeta for #ident #ty_generics #where_clause {
+            fn inject(injector: &mydi::injector::Injector) -> anyhow::Result<Self>  {
+                #[allow(unused_imports)]
+                use mydi::variant_field::{ComponentField as _, ValueField as _};
+                let variant: #variant_enum = mydi::injected_fn::call(&#select, injector)?;
+                let result = match variant {
+                    #(#match_arms),*
+                };
+                Ok(result)
+            }
+
+            #debug_line
+
+            #lifecycle_hooks
+
+            // the selector and the union of dependencies of all variants
+            fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+                #[allow(unused_imports)]
+                use mydi::variant_field::{ComponentField as _, ValueField as _};
+                let mut result = mydi::injected_fn::dependencies_names(&#select);
+                #(#dependencies)*
+                let mut unique = std::collections::HashSet::new();
+                result.retain(|(type_id, _)| unique.insert(*type_id));
+                result
+            }
+
+            // bound components of the variants are built before the enum and reused
+            fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+                #[allow(unused_imports)]
+                use mydi::variant_field::{ComponentField as _, ValueField as _};
+                let mut result: Vec<(std::any::TypeId, &'static str)> = Vec::new();
+                #(#optional_dependencies)*
+                let mut unique = std::collections::HashSet::new();
+                result.retain(|(type_id, _)| unique.insert(*type_id));
+                result
+            }
+
+            fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+                #[allow(unused_imports)]
+                use mydi::variant_field::{ComponentField as _, ValueField as _};
+                let mut result: Vec<(&'static str, std::any::TypeId, &'static str)> = Vec::new();
+                #(#named_dependencies)*
+                result
+            }
+        }
+    ))
+}
+
+// a component built on demand or a value read from the injector, see mydi::variant_field
+fn variant_field(field_type: &Type) -> TokenStream {
+    quote!((&&mydi::variant_field::VariantField::<#field_type>::new()))
+}
+
+fn read_fields(fields: &Fields) -> Result<Vec<(TokenStream, Type, FieldInjection)>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| {
+            // fields of tuple structs are initialized by index: Self { 0: ..., 1: ... }
+            let name = match field.ident.as_ref() {
+                Some(ident) => quote!( #ident ),
+                None => syn::Index::from(idx).to_token_stream(),
+            };
+            let injection = read_field_injection(field)?;
+            Ok((name, field.ty.clone(), injection))
+        })
+        .collect()
+}
+
+fn field_init(field_name: &TokenStream, field_type: &Type, injection: &FieldInjection) -> TokenStream {
+    match injection {
+        FieldInjection::Tagged(tag) => {
+            let tagged = tagged_type(field_type, tag);
+            quote!(#field_name: injector.get::<#tagged>()?.untag())
+        }
+        FieldInjection::Named(name) => quote!(#field_name: injector.get_named::<#field_type>(#name)?),
+        FieldInjection::Inject => quote!(#field_name: injector.get()?),
+        FieldInjection::Default => quote!(#field_name: Default::default()),
+        FieldInjection::DefaultPath(path) => {
+            let value = default_path_value(path);
+            quote!(#field_name: #value)
+        }
+        FieldInjection::DefaultValue(value) => quote!(#field_name: #value),
+        FieldInjection::DefaultWith(func) => {
+            quote!(#field_name: mydi::injected_fn::call(&(#func), injector)?)
+        }
+        FieldInjection::DefaultIfMissing(default) => {
+            let default = match default {
+                None => quote!(Default::default),
+                Some(syn::Expr::Path(path)) => {
+                    let value = default_path_value(path);
+                    quote!(|| #value)
+                }
+                Some(value) => quote!(|| #value),
+            };
+            quote!(#field_name: injector.get_optional::<#field_type>()?.unwrap_or_else(#default))
+        }
+        FieldInjection::Optional(inner_type) => {
+            quote!(#field_name: injector.get_optional::<#inner_type>()?)
+        }
+    }
+}
+
+// Добавление требования реализации трейта Clone для каждого дженерика
+fn add_clone_bounds(generics: &mut Generics) {
+    for param in generics.params.iter_mut() {
+        use syn::{GenericParam, TypeParamBound};
+        if let GenericParam::Type(type_param) = param {
+            type_param
+                .bounds
+                .push(TypeParamBound::Trait(syn::parse_quote!(Clone)));
+        }
+    }
+}
+
+fn debug_line_fn() -> TokenStream {
+    quote!(
+        fn debug_line() -> Option<String> {
+            let line_num = line!();
+            let file_name = file!();
+            let mut result = String::new();
+            result.push_str(file_name);
+            result.push_str(":");
+            result.push_str(line_num.to_string().as_str());
+            Some(result)
+        }
+    )
+}
+
+// registers hooks of the derived Lifecycle impl in `inject`
+// Arguments of `default_with` closures with type annotations,
+// functions and arguments without types are checked only by `verify`
+fn closure_argument_types(func: &syn::Expr) -> Vec<TokenStream> {
+    match func {
+        syn::Expr::Closure(closure) => closure
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                syn::Pat::Type(pat_type) => Some(pat_type.ty.to_token_stream()),
+                _ => None,
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+fn lifecycle_hooks_fn(attributes: &ComponentAttributes) -> TokenStream {
+    if attributes.on_start.is_none() && attributes.on_stop.is_none() {
+        return quote!();
+    }
+    quote!(
+        fn lifecycle_hooks() -> Option<mydi::lifecycle::LifecycleHooks>
+        where
+            Self: 'static,
+        {
+            Some(mydi::lifecycle::LifecycleHooks::of::<Self>())
+        }
+    )
+}
+
+fn lifecycle_impl(ident: &Ident, generics: &Generics, attributes: &ComponentAttributes) -> TokenStream {
+    if attributes.on_start.is_none() && attributes.on_stop.is_none() {
+        return quote!();
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let start = attributes.on_start.as_ref().map(|func| {
+        quote!(
+            fn start(&self) -> anyhow::Result<()> {
+                #func(self)
+            }
+        )
+    });
+    let stop = attributes.on_stop.as_ref().map(|func| {
+        quote!(
+            fn stop(&self) -> anyhow::Result<()> {
+                #func(self)
+            }
+        )
+    });
+    quote!(
+        #[automatically_derived]
+        impl #impl_generics mydi::lifecycle::Lifecycle for #ident #ty_generics #where_clause {
+            #start
+            #stop
+        }
+    )
+}
+
+enum FieldInjection {
+    Inject,
     Default,
-    DefaultFunction(proc_macro2::TokenStream),
+    // function or value, resolved by mydi::default_value
+    DefaultPath(syn::ExprPath),
+    DefaultValue(syn::Expr),
+    // function or closure whose arguments are injected
+    DefaultWith(syn::Expr),
+    // the bound value if any, otherwise the default, like `default`
+    DefaultIfMissing(Option<syn::Expr>),
+    // inner type of Option<T>
+    Optional(Type),
+    // the field is read as Tagged<T, Tag> and untagged
+    Tagged(Type),
+    // the field is read with Injector::get_named
+    Named(syn::LitStr),
+}
+
+// calls the path if it is a function, otherwise clones the value
+fn default_path_value(path: &syn::ExprPath) -> TokenStream {
+    quote!({
+        #[allow(unused_imports)]
+        use mydi::default_value::{DefaultConst as _, DefaultFn as _};
+        (&&mydi::default_value::DefaultPath(#path)).default_value()
+    })
+}
+
+fn tagged_type(field_type: &Type, tag: &Type) -> TokenStream {
+    quote!(mydi::tags::Tagged<#field_type, #tag>)
 }
 
 // generated by chat gpt
-fn read_default_value(field: &Field) -> Result<DefaultValue> {
-    let mut default_value: DefaultValue = DefaultValue::None;
+fn read_field_injection(field: &Field) -> Result<FieldInjection> {
+    let mut injection: FieldInjection = FieldInjection::Inject;
 
     for attribute in &field.attrs {
         if attribute.path().is_ident("component") {
-            if let DefaultValue::None = default_value {
+            if let FieldInjection::Inject = injection {
                 let component_args = attribute.parse_args_with(|input: ParseStream| {
                     if input.is_empty() {
                         return Err(input.error("Expected an argument after #[component(...)]."));
                     }
 
+                    if !input.peek(Token![default]) {
+                        let argument: syn::Ident = input.parse()?;
+                        return if argument == "optional" {
+                            Ok(FieldInjection::Optional(option_inner_type(&field.ty)?))
+                        } else if argument == "tag" {
+                            input.parse::<Token![=]>()?;
+                            Ok(FieldInjection::Tagged(input.parse()?))
+                        } else if argument == "named" {
+                            input.parse::<Token![=]>()?;
+                            Ok(FieldInjection::Named(input.parse()?))
+                        } else if argument == "default_if_missing" {
+                            if !input.peek(Token![=]) {
+                                return Ok(FieldInjection::DefaultIfMissing(None));
+                            }
+                            input.parse::<Token![=]>()?;
+                            Ok(FieldInjection::DefaultIfMissing(Some(input.parse()?)))
+                        } else if argument == "default_with" {
+                            input.parse::<Token![=]>()?;
+                            Ok(FieldInjection::DefaultWith(input.parse()?))
+                        } else {
+                            Err(syn::Error::new_spanned(
+                                argument,
+                                "Expected default, default_with, default_if_missing, optional, tag or named in #[component(...)].",
+                            ))
+                        };
+                    }
+
                     let _default_keyword: Token![default] = input.parse()?;
 
                     if input.peek(Token![=]) {
                         input.parse::<Token![=]>()?;
 
-                        if input.peek(syn::Ident) {
-                            let default_function: syn::Ident = input.parse()?;
-                            Ok(DefaultValue::DefaultFunction(default_function.to_token_stream()))
-                        } else {
-                            Err(input.error("Expected a user-defined function identifier after #[component(default = ...)]."))
+                        // paths may be functions or values, other expressions are values
+                        match input.parse()? {
+                            syn::Expr::Path(path) => Ok(FieldInjection::DefaultPath(path)),
+                            value => Ok(FieldInjection::DefaultValue(value)),
                         }
                     } else {
-                        Ok(DefaultValue::Default)
+                        Ok(FieldInjection::Default)
                     }
                 });
 
                 match component_args {
-                    Ok(value) => default_value = value,
+                    Ok(value) => injection = value,
                     Err(err) => return Err(err),
                 }
             } else {
@@ -168,5 +522,59 @@ fn read_default_value(field: &Field) -> Result<DefaultValue> {
         }
     }
 
-    Ok(default_value)
+    Ok(injection)
+}
+
+// extracts T from Option<T>
+fn option_inner_type(field_type: &Type) -> Result<Type> {
+    use syn::{GenericArgument, PathArguments};
+
+    if let Type::Path(type_path) = field_type {
+        if let Some(segment) = type_path.path.segments.last() {
+            if segment.ident == "Option" {
+                if let PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                    if let Some(GenericArgument::Type(inner_type)) = arguments.args.first() {
+                        return Ok(inner_type.clone());
+                    }
+                }
+            }
+        }
+    }
+    Err(syn::Error::new_spanned(
+        field_type,
+        "#[component(optional)] can only be used with Option<T> fields.",
+    ))
+}
+
+#[derive(Default)]
+struct ComponentAttributes {
+    on_start: Option<syn::Path>,
+    on_stop: Option<syn::Path>,
+    // selector of the enum variant
+    select: Option<syn::Path>,
+}
+
+// reads #[component(on_start = func, on_stop = func, select = func)] from the struct or enum attributes
+fn read_component_attributes(attrs: &[Attribute]) -> Result<ComponentAttributes> {
+    let mut attributes = ComponentAttributes::default();
+    for attribute in attrs {
+        if !attribute.path().is_ident("component") {
+            continue;
+        }
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("on_start") {
+                attributes.on_start = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("on_stop") {
+                attributes.on_stop = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("select") {
+                attributes.select = Some(meta.value()?.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("Expected on_start, on_stop or select in #[component(...)] of a type."))
+            }
+        })?;
+    }
+    Ok(attributes)
 }
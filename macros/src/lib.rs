@@ -2,6 +2,7 @@ extern crate proc_macro;
 
 mod derive_component;
 mod derive_expander;
+mod module;
 
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
@@ -17,12 +18,13 @@ use syn::{Result, Token};
 pub fn derive_inject(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let DeriveInput {
         ident,
+        vis,
         data,
         generics,
-        ..
+        attrs,
     } = parse_macro_input!(input);
 
-    derive_component::derive_inject_impl(ident, data, generics)
+    derive_component::derive_inject_impl(ident, vis, data, generics, attrs)
         .unwrap_or_else(Error::into_compile_error)
         .into()
 }
@@ -42,3 +44,14 @@ pub fn derive_expansion(input: proc_macro::TokenStream) -> proc_macro::TokenStre
         .unwrap_or_else(Error::into_compile_error)
         .into()
 }
+
+// Declares a binder, whose wiring is verified at compile time:
+// mydi::module! { instance u32 = 1; inject A; external B; }
+#[proc_macro]
+pub fn module(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let module = parse_macro_input!(input as module::Module);
+
+    module::module_impl(module)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,85 @@
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::spanned::Spanned;
+use syn::{Expr, Ident, Result, Token, Type};
+
+// instance Type = expr;
+// inject Type;
+// external Type;
+enum Statement {
+    Instance(Type, Expr),
+    Inject(Type),
+    External(Type),
+}
+
+pub(crate) struct Module {
+    statements: Vec<Statement>,
+}
+
+impl Parse for Module {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut statements = Vec::new();
+        while !input.is_empty() {
+            let keyword: Ident = input.parse()?;
+            let statement = if keyword == "instance" {
+                let field_type: Type = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                Statement::Instance(field_type, value)
+            } else if keyword == "inject" {
+                Statement::Inject(input.parse()?)
+            } else if keyword == "external" {
+                Statement::External(input.parse()?)
+            } else {
+                return Err(syn::Error::new_spanned(
+                    keyword,
+                    "Expected instance, inject or external in mydi::module!",
+                ));
+            };
+            input.parse::<Token![;]>()?;
+            statements.push(statement);
+        }
+        Ok(Self { statements })
+    }
+}
+
+pub(crate) fn module_impl(module: Module) -> Result<TokenStream> {
+    let provided_types = module.statements.iter().map(|statement| match statement {
+        Statement::Instance(provided, _)
+        | Statement::Inject(provided)
+        | Statement::External(provided) => provided,
+    });
+    // conflicting implementations are reported for duplicates
+    let provides_impls = provided_types.map(|provided| {
+        quote_spanned! { provided.span() =>
+            impl mydi::module::Provides<#provided> for __MydiModule {}
+        }
+    });
+
+    let verifications = module.statements.iter().filter_map(|statement| match statement {
+        Statement::Inject(component) => Some(quote_spanned! { component.span() =>
+            mydi::module::verify::<
+                __MydiModule,
+                <#component as mydi::component_meta::StaticDependencies>::Dependencies,
+            >();
+        }),
+        _ => None,
+    });
+
+    let bindings = module.statements.iter().filter_map(|statement| match statement {
+        Statement::Instance(instance, value) => Some(quote!(.instance::<#instance>(#value))),
+        Statement::Inject(component) => Some(quote!(.inject::<#component>())),
+        Statement::External(_) => None,
+    });
+
+    Ok(quote!({
+        struct __MydiModule;
+        #(#provides_impls)*
+        #(#verifications)*
+
+        mydi::InjectionBinder::new()
+            #(#bindings)*
+            .void()
+    }))
+}
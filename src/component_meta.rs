@@ -1,4 +1,5 @@
 use crate::injector::Injector;
+use crate::lifecycle::LifecycleHooks;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -13,6 +14,24 @@ where
 
     fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)>;
 
+    // dependencies which are injected if they are bound and are skipped otherwise
+    fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+        vec![]
+    }
+
+    // (name, type id, type name) of dependencies bound with `instance_named`
+    fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+        vec![]
+    }
+
+    // hooks declared with `#[component(on_start = ..., on_stop = ...)]`, registered by `inject`
+    fn lifecycle_hooks() -> Option<LifecycleHooks>
+    where
+        Self: 'static,
+    {
+        None
+    }
+
     // very unsafe
     // use carefully
     fn lazy() -> bool {
@@ -20,6 +39,28 @@ where
     }
 }
 
+// Dependencies known at compile time, used by `mydi::module!` to verify the wiring.
+// Implemented by `derive(Component)`, dependencies are nested pairs, for example `(A, (B, ()))`
+pub trait StaticDependencies {
+    type Dependencies;
+}
+
+impl<Inner: StaticDependencies> StaticDependencies for Box<Inner> {
+    type Dependencies = Inner::Dependencies;
+}
+
+impl<Inner: StaticDependencies> StaticDependencies for Rc<Inner> {
+    type Dependencies = Inner::Dependencies;
+}
+
+impl<Inner: StaticDependencies> StaticDependencies for Arc<Inner> {
+    type Dependencies = Inner::Dependencies;
+}
+
+impl<Inner: StaticDependencies> StaticDependencies for &'static Inner {
+    type Dependencies = Inner::Dependencies;
+}
+
 impl<Inner> ComponentMeta for Box<Inner>
 where
     Inner: ComponentMeta,
@@ -38,6 +79,14 @@ where
     fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
         Inner::dependencies_names()
     }
+
+    fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+        Inner::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+        Inner::named_dependencies_names()
+    }
 }
 
 impl<Inner> ComponentMeta for Rc<Inner>
@@ -58,6 +107,14 @@ where
     fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
         Inner::dependencies_names()
     }
+
+    fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+        Inner::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+        Inner::named_dependencies_names()
+    }
 }
 
 impl<Inner> ComponentMeta for Arc<Inner>
@@ -78,6 +135,14 @@ where
     fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
         Inner::dependencies_names()
     }
+
+    fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+        Inner::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+        Inner::named_dependencies_names()
+    }
 }
 
 impl<Inner> ComponentMeta for &'static Inner
@@ -96,4 +161,12 @@ where
     fn dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
         Inner::dependencies_names()
     }
+
+    fn optional_dependencies_names() -> Vec<(std::any::TypeId, &'static str)> {
+        Inner::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, std::any::TypeId, &'static str)> {
+        Inner::named_dependencies_names()
+    }
 }
@@ -0,0 +1,116 @@
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+// Finds strongly connected components of the graph with Tarjan's algorithm
+// and returns the shortest cycle of each component.
+// `order` makes the result stable, because the graph is stored in a HashMap.
+pub(crate) fn find_cycles(
+    graph: &HashMap<TypeId, Vec<TypeId>>,
+    order: &[TypeId],
+) -> Vec<Vec<TypeId>> {
+    let mut tarjan = Tarjan {
+        graph,
+        index: 0,
+        indexes: HashMap::new(),
+        low_links: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for type_id in order.iter().filter(|x| graph.contains_key(x)) {
+        if !tarjan.indexes.contains_key(type_id) {
+            tarjan.visit(*type_id);
+        }
+    }
+
+    let position: HashMap<_, _> = order.iter().enumerate().map(|(idx, x)| (*x, idx)).collect();
+    let mut cycles: Vec<_> = tarjan
+        .components
+        .into_iter()
+        .filter(|component| {
+            component.len() > 1 || graph[&component[0]].contains(&component[0])
+        })
+        .map(|component| {
+            let start = *component
+                .iter()
+                .min_by_key(|x| position.get(x).copied().unwrap_or(usize::MAX))
+                .unwrap();
+            let component: HashSet<_> = component.into_iter().collect();
+            shortest_cycle(graph, &component, start)
+        })
+        .collect();
+    cycles.sort_by_key(|cycle| position.get(&cycle[0]).copied().unwrap_or(usize::MAX));
+    cycles
+}
+
+struct Tarjan<'a> {
+    graph: &'a HashMap<TypeId, Vec<TypeId>>,
+    index: usize,
+    indexes: HashMap<TypeId, usize>,
+    low_links: HashMap<TypeId, usize>,
+    stack: Vec<TypeId>,
+    on_stack: HashSet<TypeId>,
+    components: Vec<Vec<TypeId>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, type_id: TypeId) {
+        self.indexes.insert(type_id, self.index);
+        self.low_links.insert(type_id, self.index);
+        self.index += 1;
+        self.stack.push(type_id);
+        self.on_stack.insert(type_id);
+
+        let graph = self.graph;
+        for dependency in graph[&type_id].iter().filter(|x| graph.contains_key(x)) {
+            if !self.indexes.contains_key(dependency) {
+                self.visit(*dependency);
+                let low_link = self.low_links[&type_id].min(self.low_links[dependency]);
+                self.low_links.insert(type_id, low_link);
+            } else if self.on_stack.contains(dependency) {
+                let low_link = self.low_links[&type_id].min(self.indexes[dependency]);
+                self.low_links.insert(type_id, low_link);
+            }
+        }
+
+        if self.low_links[&type_id] == self.indexes[&type_id] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack.remove(&member);
+                component.push(member);
+                if member == type_id {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+// BFS inside the component from `start` back to `start`.
+// The returned path doesn't repeat `start` at the end.
+fn shortest_cycle(
+    graph: &HashMap<TypeId, Vec<TypeId>>,
+    component: &HashSet<TypeId>,
+    start: TypeId,
+) -> Vec<TypeId> {
+    let mut previous: HashMap<TypeId, TypeId> = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+    while let Some(current) = queue.pop_front() {
+        for dependency in graph[&current].iter().filter(|x| component.contains(x)) {
+            if *dependency == start {
+                let mut path = vec![current];
+                while let Some(prev) = previous.get(path.last().unwrap()) {
+                    path.push(*prev);
+                }
+                path.reverse();
+                return path;
+            }
+            if !previous.contains_key(dependency) {
+                previous.insert(*dependency, current);
+                queue.push_back(*dependency);
+            }
+        }
+    }
+    vec![start]
+}
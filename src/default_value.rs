@@ -0,0 +1,25 @@
+// Values of `#[component(default = path)]`, used by the generated code of `derive(Component)`.
+// A path may name either a function without arguments or a value, such as a constant or a unit variant,
+// so the generated code calls `(&&DefaultPath(path)).default_value()`
+// and the method resolution picks `DefaultFn` for functions and `DefaultConst` otherwise.
+pub struct DefaultPath<T>(pub T);
+
+pub trait DefaultFn<R> {
+    fn default_value(&self) -> R;
+}
+
+impl<F: Fn() -> R, R> DefaultFn<R> for &DefaultPath<F> {
+    fn default_value(&self) -> R {
+        (self.0)()
+    }
+}
+
+pub trait DefaultConst<T> {
+    fn default_value(&self) -> T;
+}
+
+impl<T: Clone> DefaultConst<T> for DefaultPath<T> {
+    fn default_value(&self) -> T {
+        self.0.clone()
+    }
+}
@@ -0,0 +1,286 @@
+use crate::injection_binder::{join, make_name_shorter};
+use std::any::TypeId;
+use std::fmt;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Clone)]
+pub struct TypeInfo {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    // position of the structure in the code, if known
+    pub debug_line: Option<String>,
+}
+
+impl TypeInfo {
+    pub(crate) fn of<T: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            debug_line: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MissingDependencies {
+    pub dependent: TypeInfo,
+    pub missing: Vec<TypeInfo>,
+}
+
+// Types of the cycle in the order of dependencies, the last type depends on the first one
+#[derive(Debug, Clone)]
+pub struct Cycle {
+    pub path: Vec<TypeInfo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct NamedTypeInfo {
+    pub name: String,
+    pub type_info: TypeInfo,
+}
+
+#[derive(Debug, Clone)]
+pub struct MissingNamedDependencies {
+    pub dependent: TypeInfo,
+    pub missing: Vec<NamedTypeInfo>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Duplicate {
+    pub type_info: TypeInfo,
+    pub binding_sites: Vec<String>,
+}
+
+// `short_types` only affects the `Display` implementation, see `InjectionBinder::verify`
+#[derive(Debug)]
+pub enum Error {
+    MissingDependencies {
+        dependencies: Vec<MissingDependencies>,
+        short_types: bool,
+    },
+    MissingNamedDependencies {
+        dependencies: Vec<MissingNamedDependencies>,
+        short_types: bool,
+    },
+    Cycle {
+        cycles: Vec<Cycle>,
+        short_types: bool,
+    },
+    Duplicates {
+        duplicates: Vec<Duplicate>,
+        short_types: bool,
+    },
+    NamedDuplicates {
+        names: Vec<NamedTypeInfo>,
+        short_types: bool,
+    },
+    NestedLazy {
+        types: Vec<TypeInfo>,
+        short_types: bool,
+    },
+    MissingOverrides {
+        types: Vec<TypeInfo>,
+        names: Vec<NamedTypeInfo>,
+        short_types: bool,
+    },
+    // transient types with lifecycle hooks, whose start and stop would run on different instances
+    TransientLifecycle {
+        types: Vec<TypeInfo>,
+        short_types: bool,
+    },
+    // bindings which are not used by the root, see `InjectionBinder::verify_unused`
+    Unused {
+        types: Vec<TypeInfo>,
+        short_types: bool,
+    },
+    AsyncBuilders {
+        types: Vec<TypeInfo>,
+    },
+    BuilderFailed {
+        type_info: TypeInfo,
+        source: anyhow::Error,
+    },
+    LifecycleFailed {
+        type_info: TypeInfo,
+        // "start" or "stop"
+        hook: &'static str,
+        source: anyhow::Error,
+    },
+    MissingValue {
+        type_info: TypeInfo,
+    },
+    MissingNamedValue {
+        named: NamedTypeInfo,
+    },
+    Multiple(Vec<Error>),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingDependencies {
+                dependencies,
+                short_types,
+            } => {
+                writeln!(f, "Missing injection values:")?;
+                for dependency in dependencies {
+                    let name = make_name_shorter(dependency.dependent.type_name, *short_types);
+                    write!(f, "for type {name}")?;
+                    if let Some(debug_line) = &dependency.dependent.debug_line {
+                        write!(f, "\n at {debug_line}")?;
+                    }
+                    let missing = names(&dependency.missing, *short_types);
+                    write!(f, "\nmissing dependencies: {missing}\n\n")?;
+                }
+                Ok(())
+            }
+            Error::MissingNamedDependencies {
+                dependencies,
+                short_types,
+            } => {
+                writeln!(f, "Missing named injection values:")?;
+                for dependency in dependencies {
+                    let name = make_name_shorter(dependency.dependent.type_name, *short_types);
+                    write!(f, "for type {name}")?;
+                    if let Some(debug_line) = &dependency.dependent.debug_line {
+                        write!(f, "\n at {debug_line}")?;
+                    }
+                    let missing = named(&dependency.missing, *short_types);
+                    write!(f, "\nmissing dependencies: {missing}\n\n")?;
+                }
+                Ok(())
+            }
+            Error::Cycle {
+                cycles,
+                short_types,
+            } => {
+                write!(f, "Dependencies cycle (one or more) found :")?;
+                for cycle in cycles {
+                    let path = cycle
+                        .path
+                        .iter()
+                        .chain(cycle.path.first())
+                        .map(|x| make_name_shorter(x.type_name, *short_types));
+                    write!(f, "\n{}", join(path, " -> "))?;
+                    for type_info in cycle.path.iter() {
+                        if let Some(debug_line) = &type_info.debug_line {
+                            let name = make_name_shorter(type_info.type_name, *short_types);
+                            write!(f, "\n {name} at {debug_line}")?;
+                        }
+                    }
+                    if let (Some(first), Some(last)) = (cycle.path.first(), cycle.path.last()) {
+                        let first = make_name_shorter(first.type_name, *short_types);
+                        let last = make_name_shorter(last.type_name, *short_types);
+                        write!(f, "\nthe cycle can be broken by injecting Lazy<{first}> into {last}")?;
+                    }
+                }
+                Ok(())
+            }
+            Error::Duplicates {
+                duplicates,
+                short_types,
+            } => {
+                let duplicates_names = join(
+                    duplicates
+                        .iter()
+                        .map(|x| make_name_shorter(x.type_info.type_name, *short_types)),
+                    ", ",
+                );
+                writeln!(f, "Dependencies duplications found: {duplicates_names}")?;
+                for duplicate in duplicates {
+                    let name = make_name_shorter(duplicate.type_info.type_name, *short_types);
+                    writeln!(f, "for type {name} bound at:")?;
+                    for site in duplicate.binding_sites.iter() {
+                        writeln!(f, " {site}")?;
+                    }
+                }
+                write!(f, "use override_instance or override_binder to replace bindings intentionally")
+            }
+            Error::NamedDuplicates { names, short_types } => {
+                let names = named(names, *short_types);
+                write!(f, "Named values duplications found: {names}")
+            }
+            Error::NestedLazy { types, short_types } => {
+                let names = names(types, *short_types);
+                write!(f, "Nested lazy dependencies: {names}")
+            }
+            Error::MissingOverrides {
+                types,
+                names: named_types,
+                short_types,
+            } => {
+                let names = vec![names(types, *short_types), named(named_types, *short_types)];
+                let names = join(names.into_iter().filter(|x| !x.is_empty()), ", ");
+                write!(f, "Overridden types were never bound: {names}")
+            }
+            Error::TransientLifecycle { types, short_types } => {
+                let names = names(types, *short_types);
+                write!(f, "Lifecycle hooks can't be used with transient types: {names}")
+            }
+            Error::Unused { types, short_types } => {
+                let names = names(types, *short_types);
+                write!(f, "Unused bindings found: {names}")
+            }
+            Error::AsyncBuilders { types } => {
+                let names = names(types, false);
+                write!(f, "Async builders found, use build_async instead: {names}")
+            }
+            Error::BuilderFailed { type_info, .. } => {
+                let name = type_info.type_name;
+                write!(f, "Failed to build {name}")
+            }
+            Error::LifecycleFailed { type_info, hook, .. } => {
+                let name = type_info.type_name;
+                write!(f, "Failed to {hook} {name}")
+            }
+            Error::MissingValue { type_info } => {
+                let name = type_info.type_name;
+                write!(f, "Missing value of type {name}")
+            }
+            Error::MissingNamedValue { named } => {
+                let name = named.type_info.type_name;
+                write!(f, "Missing value of type {name} named \"{}\"", named.name)
+            }
+            // causes of nested errors are printed here, as `source` returns only one error
+            Error::Multiple(errors) => {
+                let messages = errors.iter().map(|err| {
+                    let mut message = err.to_string();
+                    let mut source = std::error::Error::source(err);
+                    while let Some(cause) = source {
+                        message = format!("{message}: {cause}");
+                        source = cause.source();
+                    }
+                    message
+                });
+                write!(f, "{}", join(messages, "\n"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::BuilderFailed { source, .. } | Error::LifecycleFailed { source, .. } => {
+                Some(source.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+fn names(types: &[TypeInfo], short_types: bool) -> String {
+    let names = types
+        .iter()
+        .map(|x| make_name_shorter(x.type_name, short_types));
+    join(names, ", ")
+}
+
+fn named(types: &[NamedTypeInfo], short_types: bool) -> String {
+    let names = types.iter().map(|x| {
+        let type_name = make_name_shorter(x.type_info.type_name, short_types);
+        format!("\"{}\": {type_name}", x.name)
+    });
+    join(names, ", ")
+}
@@ -0,0 +1,147 @@
+use std::any::TypeId;
+use std::fmt::Write;
+
+// How the type was bound, used to style nodes of the exported graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingKind {
+    // `instance`
+    Instance,
+    // `inject` of a `Component`
+    Component,
+    // `inject_fn` and its variants
+    Function,
+    // `inject_async_fn`
+    AsyncFunction,
+    // `inject::<Lazy<T>>`
+    Lazy,
+    // `auto`, `auto_box` and `auto_arc`
+    Auto,
+    // collections of `add_to_set` and `add_to_map`
+    Multibinding,
+}
+
+// Description of a bound type, see `InjectionBinder::bindings` and `Injector::bindings`
+#[derive(Debug, Clone)]
+pub struct Binding {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    // type name without the module path
+    pub short_name: &'static str,
+    pub kind: BindingKind,
+    pub dependencies: Vec<(TypeId, &'static str)>,
+    pub debug_line: Option<String>,
+    pub transient: bool,
+    // whether the value is already constructed and stored
+    pub built: bool,
+}
+
+pub(crate) struct Node {
+    pub(crate) name: String,
+    // None for types which are required but not bound
+    pub(crate) kind: Option<BindingKind>,
+    pub(crate) debug_line: Option<String>,
+}
+
+pub(crate) struct Edge {
+    pub(crate) from: usize,
+    pub(crate) to: usize,
+    pub(crate) lazy: bool,
+    pub(crate) optional: bool,
+}
+
+// Nodes are referenced by edges by their index
+pub(crate) struct Graph {
+    pub(crate) nodes: Vec<Node>,
+    pub(crate) edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub(crate) fn to_dot(&self) -> String {
+        let mut result = String::new();
+        result.push_str("digraph dependencies {\n");
+        result.push_str("    node [shape=box];\n");
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let label = escape_dot(&node.name);
+            let style = match node.kind {
+                Some(BindingKind::Instance) => "shape=ellipse, style=filled, fillcolor=lightgrey",
+                Some(BindingKind::Component) => "style=filled, fillcolor=white",
+                Some(BindingKind::Function) => "style=rounded",
+                Some(BindingKind::AsyncFunction) => "style=\"rounded,bold\"",
+                Some(BindingKind::Lazy) => "style=dashed",
+                Some(BindingKind::Auto) => "style=filled, fillcolor=lightblue",
+                Some(BindingKind::Multibinding) => "shape=box3d",
+                None => "style=dotted, color=red",
+            };
+            write!(result, "    n{idx} [label=\"{label}\", {style}").unwrap();
+            if let Some(debug_line) = &node.debug_line {
+                write!(result, ", tooltip=\"{}\"", escape_dot(debug_line)).unwrap();
+            }
+            result.push_str("];\n");
+        }
+        for edge in self.edges.iter() {
+            write!(result, "    n{} -> n{}", edge.from, edge.to).unwrap();
+            if edge.lazy {
+                result.push_str(" [style=dashed]");
+            } else if edge.optional {
+                result.push_str(" [style=dotted]");
+            }
+            result.push_str(";\n");
+        }
+        result.push_str("}\n");
+        result
+    }
+
+    pub(crate) fn to_mermaid(&self) -> String {
+        let mut result = String::new();
+        result.push_str("graph TD\n");
+        for (idx, node) in self.nodes.iter().enumerate() {
+            let label = escape_mermaid(&node.name);
+            let (open, close) = match node.kind {
+                Some(BindingKind::Instance) => ("([", "])"),
+                Some(BindingKind::Function | BindingKind::AsyncFunction) => ("(", ")"),
+                Some(BindingKind::Multibinding) => ("[[", "]]"),
+                _ => ("[", "]"),
+            };
+            writeln!(result, "    n{idx}{open}\"{label}\"{close}").unwrap();
+        }
+        for edge in self.edges.iter() {
+            let arrow = if edge.lazy || edge.optional {
+                "-.->"
+            } else {
+                "-->"
+            };
+            writeln!(result, "    n{} {arrow} n{}", edge.from, edge.to).unwrap();
+        }
+
+        let classes = [
+            (Some(BindingKind::Instance), "instance", "fill:#eeeeee"),
+            (Some(BindingKind::Lazy), "lazy", "stroke-dasharray:5 5"),
+            (Some(BindingKind::Auto), "auto", "fill:#add8e6"),
+            (None, "missing", "stroke:#ff0000,stroke-dasharray:2 2"),
+        ];
+        for (kind, class, style) in classes {
+            let nodes: Vec<_> = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(_, node)| node.kind == kind)
+                .map(|(idx, _)| format!("n{idx}"))
+                .collect();
+            if !nodes.is_empty() {
+                writeln!(result, "    classDef {class} {style}").unwrap();
+                writeln!(result, "    class {} {class}", nodes.join(",")).unwrap();
+            }
+        }
+        result
+    }
+}
+
+fn escape_dot(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
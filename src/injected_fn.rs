@@ -0,0 +1,49 @@
+use crate::injector::Injector;
+use crate::tuples::TupleInjectTypes;
+use std::any::TypeId;
+
+// Functions whose arguments are read from the injector,
+// used by the generated code of `derive(Component)`, for example for enum selectors
+pub trait InjectedFn<Args, R> {
+    fn call(&self, injector: &Injector) -> crate::error::Result<R>;
+
+    fn dependencies_names() -> Vec<(TypeId, &'static str)>;
+}
+
+// Helpers to infer the arguments from the function value, as function types can't be named
+pub fn call<Args, R, F: InjectedFn<Args, R>>(f: &F, injector: &Injector) -> crate::error::Result<R> {
+    f.call(injector)
+}
+
+pub fn dependencies_names<Args, R, F: InjectedFn<Args, R>>(_: &F) -> Vec<(TypeId, &'static str)> {
+    F::dependencies_names()
+}
+
+macro_rules! build_injected_fn {
+    ($($arg_type:ident),*) => {
+        impl<F, R, $($arg_type: Clone + 'static),*> InjectedFn<($($arg_type,)*), R> for F
+        where
+            F: Fn($($arg_type),*) -> R,
+        {
+            #[allow(non_snake_case)]
+            fn call(&self, injector: &Injector) -> crate::error::Result<R> {
+                let ($($arg_type,)*) = <($($arg_type,)*)>::read_from_injector(injector)?;
+                Ok(self($($arg_type),*))
+            }
+
+            fn dependencies_names() -> Vec<(TypeId, &'static str)> {
+                <($($arg_type,)*)>::dependencies_names()
+            }
+        }
+    }
+}
+
+build_injected_fn!();
+build_injected_fn!(Arg1);
+build_injected_fn!(Arg1, Arg2);
+build_injected_fn!(Arg1, Arg2, Arg3);
+build_injected_fn!(Arg1, Arg2, Arg3, Arg4);
+build_injected_fn!(Arg1, Arg2, Arg3, Arg4, Arg5);
+build_injected_fn!(Arg1, Arg2, Arg3, Arg4, Arg5, Arg6);
+build_injected_fn!(Arg1, Arg2, Arg3, Arg4, Arg5, Arg6, Arg7);
+build_injected_fn!(Arg1, Arg2, Arg3, Arg4, Arg5, Arg6, Arg7, Arg8);
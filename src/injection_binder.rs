@@ -1,26 +1,62 @@
 use crate::component_meta::ComponentMeta;
+use crate::cycles::find_cycles;
+use crate::error::{
+    Cycle, Duplicate, Error, MissingDependencies, MissingNamedDependencies, NamedTypeInfo, Result,
+    TypeInfo,
+};
 use crate::expander::ComponentExpander;
+use crate::graph::{Binding, BindingKind, Edge, Graph, Node};
 use crate::injector::Injector;
+use crate::join::JoinAll;
+use crate::lifecycle::{Lifecycle, LifecycleHooks};
+use crate::multibinding::Multibinding;
+use crate::report::{BuildReport, BuildReportEntry};
+use crate::thread_safety::{
+    AnyValue, AsyncBuilder, BoxFuture, Builder, SendSafety, ThreadSafety,
+};
+use crate::trace::{self, BuilderSpan};
 use crate::tuples::TupleInjectTypes;
 use std::any::{type_name, Any, TypeId};
 use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::hash::Hash;
 use std::marker::PhantomData;
 use std::mem;
+use std::panic::Location;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// name and type of a named value
+pub(crate) type NamedKey = (String, TypeId);
 
 #[derive(Default)]
 pub struct InjectionBinder<LastType> {
-    static_values: HashMap<TypeId, Box<dyn Any + 'static>>,
-    builders: Vec<(
-        TypeId,
-        Box<dyn Fn(&Injector) -> anyhow::Result<Box<dyn Any + 'static>>>,
-    )>,
+    static_values: HashMap<TypeId, Box<AnyValue>>,
+    named_values: HashMap<NamedKey, Box<AnyValue>>,
+    builders: Vec<(TypeId, Box<Builder>)>,
+    async_builders: Vec<(TypeId, Box<AsyncBuilder>)>,
+    multibindings: HashMap<TypeId, Multibinding>,
 
     requirements_graph: Vec<(TypeId, Vec<TypeId>)>,
     type_names: HashMap<TypeId, &'static str>,
     debug_lines: HashMap<TypeId, String>,
+    binding_kinds: HashMap<TypeId, BindingKind>,
+    // places where the types were bound, used to report duplicates
+    binding_sites: HashMap<TypeId, Vec<&'static Location<'static>>>,
 
     lazy_types: HashSet<TypeId>,
+    // (type, dependency) pairs for dependencies which are injected only if they are bound
+    optional_requirements: HashSet<(TypeId, TypeId)>,
+    // (type, named dependency) pairs
+    named_requirements: Vec<(TypeId, NamedKey)>,
+    // named values which were bound more than once
+    named_duplicates: Vec<NamedKey>,
+    transient_types: HashSet<TypeId>,
+    lifecycle_hooks: Vec<LifecycleHooks>,
+
+    // overridden types that were never bound, reported by `verify`
+    missing_overrides: Vec<TypeId>,
+    missing_named_overrides: Vec<NamedKey>,
 
     _phantom_data: PhantomData<LastType>,
 }
@@ -39,11 +75,23 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
     fn change_type<T>(self) -> InjectionBinder<T> {
         InjectionBinder {
             static_values: self.static_values,
+            named_values: self.named_values,
             builders: self.builders,
+            async_builders: self.async_builders,
+            multibindings: self.multibindings,
             requirements_graph: self.requirements_graph,
             type_names: self.type_names,
             debug_lines: self.debug_lines,
+            binding_kinds: self.binding_kinds,
+            binding_sites: self.binding_sites,
             lazy_types: self.lazy_types,
+            optional_requirements: self.optional_requirements,
+            named_requirements: self.named_requirements,
+            named_duplicates: self.named_duplicates,
+            transient_types: self.transient_types,
+            lifecycle_hooks: self.lifecycle_hooks,
+            missing_overrides: self.missing_overrides,
+            missing_named_overrides: self.missing_named_overrides,
             _phantom_data: PhantomData,
         }
     }
@@ -55,64 +103,376 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
     pub fn merge<OtherLast>(mut self, mut other: InjectionBinder<OtherLast>) -> Self {
         self.static_values
             .extend(mem::take(&mut other.static_values));
+        for (key, value) in mem::take(&mut other.named_values) {
+            self.insert_named(key, value);
+        }
         self.builders.extend(mem::take(&mut other.builders));
-        self.requirements_graph
-            .extend(mem::take(&mut other.requirements_graph));
+        self.async_builders
+            .extend(mem::take(&mut other.async_builders));
+        for (type_id, requirements) in mem::take(&mut other.requirements_graph) {
+            // multibindings from both binders are merged into a single collection
+            let is_common_multibinding = self.multibindings.contains_key(&type_id)
+                && other.multibindings.contains_key(&type_id);
+            let existing = self
+                .requirements_graph
+                .iter_mut()
+                .find(|(id, _)| is_common_multibinding && *id == type_id);
+            match existing {
+                Some((_, existing_requirements)) => existing_requirements.extend(requirements),
+                None => self.requirements_graph.push((type_id, requirements)),
+            }
+        }
+        for (type_id, multibinding) in mem::take(&mut other.multibindings) {
+            match self.multibindings.get_mut(&type_id) {
+                Some(existing) => existing.merge(multibinding),
+                None => {
+                    self.multibindings.insert(type_id, multibinding);
+                }
+            }
+        }
         self.type_names.extend(mem::take(&mut other.type_names));
         self.debug_lines.extend(mem::take(&mut other.debug_lines));
+        self.binding_kinds
+            .extend(mem::take(&mut other.binding_kinds));
+        for (type_id, sites) in mem::take(&mut other.binding_sites) {
+            self.binding_sites.entry(type_id).or_default().extend(sites);
+        }
         self.lazy_types.extend(mem::take(&mut other.lazy_types));
+        self.optional_requirements
+            .extend(mem::take(&mut other.optional_requirements));
+        self.named_requirements
+            .extend(mem::take(&mut other.named_requirements));
+        self.named_duplicates
+            .extend(mem::take(&mut other.named_duplicates));
+        self.transient_types
+            .extend(mem::take(&mut other.transient_types));
+        self.lifecycle_hooks
+            .extend(mem::take(&mut other.lifecycle_hooks));
+        self.missing_overrides
+            .extend(mem::take(&mut other.missing_overrides));
+        self.missing_named_overrides
+            .extend(mem::take(&mut other.missing_named_overrides));
 
         self
     }
 
-    pub fn instance<X: Any + Clone + 'static>(mut self, x: X) -> Self {
+    // Replaces bindings of all types and named values bound in the other binder, for example with mocks in tests.
+    // Every overridden type must be bound in this binder, otherwise `verify` fails
+    pub fn override_binder<OtherLast>(mut self, other: InjectionBinder<OtherLast>) -> Self {
+        let overridden_types: HashSet<_> = other
+            .requirements_graph
+            .iter()
+            .map(|(type_id, _)| *type_id)
+            .collect();
+        for type_id in overridden_types {
+            self.override_binding(type_id);
+        }
+        for key in other.named_values.keys() {
+            self.override_named_binding(key);
+        }
+        self.merge(other)
+    }
+
+    // Replaces the named value, the name must be bound before
+    pub fn override_named<X: Any + Clone + ThreadSafety + 'static>(
+        mut self,
+        name: impl Into<String>,
+        x: X,
+    ) -> Self {
+        let name = name.into();
+        self.override_named_binding(&(name.clone(), TypeId::of::<X>()));
+        self.instance_named(name, x)
+    }
+
+    fn override_named_binding(&mut self, key: &NamedKey) {
+        if self.named_values.remove(key).is_none() {
+            self.missing_named_overrides.push(key.clone());
+            return;
+        }
+        self.named_duplicates.retain(|x| x != key);
+    }
+
+    // Replaces the binding of the type with the instance, the type must be bound before
+    #[track_caller]
+    pub fn override_instance<X: Any + Clone + ThreadSafety + 'static>(mut self, x: X) -> Self {
+        self.override_binding(TypeId::of::<X>());
+        self.instance(x)
+    }
+
+    // Replaces the binding of the type with the function, the type must be bound before
+    #[track_caller]
+    pub fn override_fn<
+        R: Any + Clone + ThreadSafety + 'static,
+        In: TupleInjectTypes,
+        FN: Fn(In) -> R + ThreadSafety + 'static,
+    >(
+        mut self,
+        f: FN,
+    ) -> InjectionBinder<R> {
+        self.override_binding(TypeId::of::<R>());
+        self.inject_fn(f)
+    }
+
+    fn override_binding(&mut self, type_id: TypeId) {
+        let bound = self
+            .requirements_graph
+            .iter()
+            .any(|(id, _)| *id == type_id);
+        if !bound {
+            self.missing_overrides.push(type_id);
+            return;
+        }
+
+        self.static_values.remove(&type_id);
+        self.builders.retain(|(id, _)| *id != type_id);
+        self.async_builders.retain(|(id, _)| *id != type_id);
+        self.multibindings.remove(&type_id);
+        self.requirements_graph.retain(|(id, _)| *id != type_id);
+        self.debug_lines.remove(&type_id);
+        self.binding_kinds.remove(&type_id);
+        self.binding_sites.remove(&type_id);
+        self.lazy_types.remove(&type_id);
+        self.optional_requirements.retain(|(id, _)| *id != type_id);
+        self.named_requirements.retain(|(id, _)| *id != type_id);
+        self.transient_types.remove(&type_id);
+        self.lifecycle_hooks.retain(|hooks| hooks.type_id != type_id);
+    }
+
+    #[track_caller]
+    pub fn instance<X: Any + Clone + ThreadSafety + 'static>(mut self, x: X) -> Self {
         let type_id = TypeId::of::<X>();
         self.static_values.insert(type_id, Box::new(x));
 
         self.type_names.insert(type_id, type_name::<X>());
-        self.requirements_graph.push((type_id.clone(), vec![]));
+        self.binding_kinds.insert(type_id, BindingKind::Instance);
+        self.requirements_graph.push((type_id, vec![]));
+        self.binding_sites
+            .entry(type_id)
+            .or_default()
+            .push(Location::caller());
 
         self
     }
 
-    pub fn inject<X: Any + ComponentMeta + Clone + 'static>(self) -> InjectionBinder<X> {
-        self.inject_fn_raw::<X>(
+    // Binds the value by the name and the type, so several values of the same type can be bound,
+    // for example config values. Named values are read with `Injector::get_named`
+    // or with `#[component(named = "...")]` fields
+    pub fn instance_named<X: Any + Clone + ThreadSafety + 'static>(
+        mut self,
+        name: impl Into<String>,
+        x: X,
+    ) -> Self {
+        let type_id = TypeId::of::<X>();
+        self.type_names.insert(type_id, type_name::<X>());
+        self.insert_named((name.into(), type_id), Box::new(x));
+        self
+    }
+
+    fn insert_named(&mut self, key: NamedKey, value: Box<AnyValue>) {
+        if self.named_values.contains_key(&key) && !self.named_duplicates.contains(&key) {
+            self.named_duplicates.push(key.clone());
+        }
+        self.named_values.insert(key, value);
+    }
+
+    #[track_caller]
+    pub fn inject<X: Any + ComponentMeta + Clone + ThreadSafety + 'static>(
+        self,
+    ) -> InjectionBinder<X> {
+        let optional_dependencies = X::optional_dependencies_names();
+        let named_dependencies = X::named_dependencies_names();
+        let dependencies_names = X::dependencies_names()
+            .into_iter()
+            .chain(optional_dependencies.iter().cloned())
+            .collect();
+        let mut result = self.inject_fn_raw::<X>(
             move |x: &Injector| -> anyhow::Result<X> {
                 let result = X::inject(x)?;
                 Ok(result)
             },
-            X::dependencies_names(),
+            dependencies_names,
             X::debug_line(),
             X::lazy(),
-        )
+        );
+
+        let type_id = TypeId::of::<X>();
+        if let Some(hooks) = X::lifecycle_hooks() {
+            result.lifecycle_hooks.push(hooks);
+        }
+        for (dependency_id, _) in optional_dependencies {
+            result.optional_requirements.insert((type_id, dependency_id));
+        }
+        for (name, dependency_id, dependency_name) in named_dependencies {
+            result.type_names.insert(dependency_id, dependency_name);
+            result
+                .named_requirements
+                .push((type_id, (name.to_string(), dependency_id)));
+        }
+        let kind = if X::lazy() {
+            BindingKind::Lazy
+        } else {
+            BindingKind::Component
+        };
+        result.kind(kind)
+    }
+
+    // The component is built again on every `Injector::get` instead of being built once in `build`
+    #[track_caller]
+    pub fn inject_transient<X: Any + ComponentMeta + Clone + ThreadSafety + 'static>(
+        self,
+    ) -> InjectionBinder<X> {
+        self.inject::<X>().transient()
     }
 
-    pub fn auto_box(self) -> Self {
+    #[track_caller]
+    pub fn auto_box(self) -> Self
+    where
+        LastType: ThreadSafety,
+    {
         self.inject_fn(|(x,)| -> Box<LastType> { Box::new(x) })
+            .kind(BindingKind::Auto)
             .change_type::<LastType>()
     }
 
-    pub fn auto_arc(self) -> Self {
+    #[track_caller]
+    pub fn auto_arc(self) -> Self
+    where
+        LastType: ThreadSafety,
+    {
         self.inject_fn(|(x,)| -> Arc<LastType> { Arc::new(x) })
+            .kind(BindingKind::Auto)
             .change_type::<LastType>()
     }
 
-    pub fn auto<Din: Clone + 'static>(self, f: impl Fn(LastType) -> Din + 'static) -> Self {
+    #[track_caller]
+    pub fn auto<Din: Clone + ThreadSafety + 'static>(
+        self,
+        f: impl Fn(LastType) -> Din + ThreadSafety + 'static,
+    ) -> Self {
         self.inject_fn(move |(x,)| -> Din { f(x) })
+            .kind(BindingKind::Auto)
             .change_type::<LastType>()
     }
 
-    pub fn inject_fn<R: Any + Clone + 'static, In: TupleInjectTypes, FN: Fn(In) -> R + 'static>(
+    // Registers start/stop hooks of the last added type, see `Injector::start_all`.
+    // Hooks declared with `#[component(on_start = ..., on_stop = ...)]` are registered by `inject`
+    pub fn lifecycle(mut self) -> Self
+    where
+        LastType: Lifecycle,
+    {
+        let type_id = TypeId::of::<LastType>();
+        self.lifecycle_hooks.retain(|hooks| hooks.type_id != type_id);
+        self.lifecycle_hooks.push(LifecycleHooks::of::<LastType>());
+        self
+    }
+
+    #[track_caller]
+    pub fn inject_fn<
+        R: Any + Clone + ThreadSafety + 'static,
+        In: TupleInjectTypes,
+        FN: Fn(In) -> R + ThreadSafety + 'static,
+    >(
         self,
         f: FN,
     ) -> InjectionBinder<R> {
         self.inject_fn_ok::<R, In, _>(move |tuple| Ok(f(tuple)))
     }
 
+    // Adds the last type to `Vec<T>`, which contains all types added this way
+    // and can be injected like any other type.
+    // Works across merged binders.
+    pub fn add_to_set<T: Clone + ThreadSafety + 'static>(
+        self,
+        f: impl Fn(LastType) -> T + ThreadSafety + 'static,
+    ) -> Self
+    where
+        LastType: ThreadSafety,
+    {
+        self.add_to_multibinding::<Vec<T>, T>(Multibinding::set::<T>, f)
+    }
+
+    // Adds the last type with the key to `HashMap<K, V>`, which contains all types added this way
+    // and can be injected like any other type.
+    // Works across merged binders, duplicate keys are reported during the build.
+    pub fn add_to_map<
+        K: Eq + Hash + Clone + ThreadSafety + 'static,
+        V: Clone + ThreadSafety + 'static,
+    >(
+        self,
+        key: K,
+        f: impl Fn(LastType) -> V + ThreadSafety + 'static,
+    ) -> Self
+    where
+        LastType: ThreadSafety,
+    {
+        self.add_to_multibinding::<HashMap<K, V>, (K, V)>(Multibinding::map::<K, V>, move |x| {
+            (key.clone(), f(x))
+        })
+    }
+
+    fn add_to_multibinding<Collection: 'static, Item: ThreadSafety + 'static>(
+        mut self,
+        empty: fn() -> Multibinding,
+        f: impl Fn(LastType) -> Item + ThreadSafety + 'static,
+    ) -> Self
+    where
+        LastType: ThreadSafety,
+    {
+        let collection_id = TypeId::of::<Collection>();
+        let item_id = TypeId::of::<LastType>();
+        let contribution: Box<Builder> = Box::new(move |injector: &Injector| {
+            let item: Box<AnyValue> = Box::new(f(injector.get::<LastType>()?));
+            Ok(item)
+        });
+        self.multibindings
+            .entry(collection_id)
+            .or_insert_with(empty)
+            .add(contribution);
+
+        let existing = self
+            .requirements_graph
+            .iter_mut()
+            .find(|(type_id, _)| *type_id == collection_id);
+        match existing {
+            Some((_, requirements)) => requirements.push(item_id),
+            None => self.requirements_graph.push((collection_id, vec![item_id])),
+        }
+        self.type_names
+            .insert(collection_id, type_name::<Collection>());
+        self.binding_kinds
+            .insert(collection_id, BindingKind::Multibinding);
+        self.type_names.insert(item_id, type_name::<LastType>());
+
+        self
+    }
+
+    #[track_caller]
+    pub fn inject_fn_transient<
+        R: Any + Clone + ThreadSafety + 'static,
+        In: TupleInjectTypes,
+        FN: Fn(In) -> R + ThreadSafety + 'static,
+    >(
+        self,
+        f: FN,
+    ) -> InjectionBinder<R> {
+        self.inject_fn(f).transient()
+    }
+
+    fn kind(mut self, kind: BindingKind) -> Self {
+        self.binding_kinds.insert(TypeId::of::<LastType>(), kind);
+        self
+    }
+
+    fn transient(mut self) -> Self {
+        self.transient_types.insert(TypeId::of::<LastType>());
+        self
+    }
+
+    #[track_caller]
     pub fn inject_fn_ok<
-        R: Any + Clone + 'static,
+        R: Any + Clone + ThreadSafety + 'static,
         In: TupleInjectTypes,
-        FN: Fn(In) -> anyhow::Result<R> + 'static,
+        FN: Fn(In) -> anyhow::Result<R> + ThreadSafety + 'static,
     >(
         self,
         f: FN,
@@ -129,20 +489,66 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
         )
     }
 
-    pub fn inject_fn_raw<X: Any + Clone + 'static>(
+    #[track_caller]
+    pub fn inject_fn_raw<X: Any + Clone + ThreadSafety + 'static>(
         mut self,
-        f: impl Fn(&Injector) -> anyhow::Result<X> + 'static,
+        f: impl Fn(&Injector) -> anyhow::Result<X> + ThreadSafety + 'static,
         dependencies_names: Vec<(TypeId, &'static str)>,
         debug_line: Option<String>,
         lazy: bool,
     ) -> InjectionBinder<X> {
-        let func =
-            Box::new(move |x: &Injector| -> anyhow::Result<Box<dyn Any>> { Ok(Box::new((f)(x)?)) });
+        let func: Box<Builder> =
+            Box::new(move |x: &Injector| -> anyhow::Result<Box<AnyValue>> { Ok(Box::new((f)(x)?)) });
         let type_id = TypeId::of::<X>();
-        self.builders.push((type_id.clone(), func));
+        self.builders.push((type_id, func));
+        self.binding_kinds.insert(type_id, BindingKind::Function);
 
+        self.add_requirements::<X>(dependencies_names, debug_line, lazy);
+        self.change_type::<X>()
+    }
+
+    #[track_caller]
+    pub fn inject_async_fn<
+        R: Any + Clone + ThreadSafety + 'static,
+        In: TupleInjectTypes,
+        Fut: Future<Output = anyhow::Result<R>> + SendSafety + 'static,
+        FN: Fn(In) -> Fut + ThreadSafety + 'static,
+    >(
+        mut self,
+        f: FN,
+    ) -> InjectionBinder<R> {
+        let func: Box<AsyncBuilder> = Box::new(move |injector: &Injector| -> BoxFuture<_> {
+            // dependencies are read before the future starts, so the future doesn't borrow the injector
+            let tuple = In::read_from_injector(injector);
+            let future = tuple.map(&f);
+            Box::pin(async move {
+                let result = future?.await?;
+                let result: Box<AnyValue> = Box::new(result);
+                Ok(result)
+            })
+        });
+        let type_id = TypeId::of::<R>();
+        self.async_builders.push((type_id, func));
+        self.binding_kinds.insert(type_id, BindingKind::AsyncFunction);
+
+        self.add_requirements::<R>(In::dependencies_names(), None, false);
+        self.change_type::<R>()
+    }
+
+    #[track_caller]
+    fn add_requirements<X: 'static>(
+        &mut self,
+        dependencies_names: Vec<(TypeId, &'static str)>,
+        debug_line: Option<String>,
+        lazy: bool,
+    ) {
+        let type_id = TypeId::of::<X>();
         let requirements = dependencies_names.iter().map(|(id, _)| *id).collect();
         self.requirements_graph.push((type_id, requirements));
+        self.binding_sites
+            .entry(type_id)
+            .or_default()
+            .push(Location::caller());
 
         for (type_id, type_name) in dependencies_names {
             self.type_names.insert(type_id, type_name);
@@ -156,67 +562,197 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
         if let Some(debug_line) = debug_line {
             self.debug_lines.insert(type_id, debug_line);
         }
+    }
 
-        self.change_type::<X>()
+    // Graphviz DOT representation of the dependencies, for example for architecture diagrams.
+    // Lazy edges are dashed, optional edges are dotted, unbound types are red.
+    pub fn to_dot(&self, short_types: bool) -> String {
+        self.graph(short_types).to_dot()
+    }
+
+    // Mermaid flowchart representation of the dependencies, styled like `to_dot`
+    pub fn to_mermaid(&self, short_types: bool) -> String {
+        self.graph(short_types).to_mermaid()
+    }
+
+    // All bound types in the order of binding, nothing is built yet except instances
+    pub fn bindings(&self) -> Vec<Binding> {
+        self.requirements_graph
+            .iter()
+            .map(|(type_id, requirements)| {
+                let type_name = self.type_names.get(type_id).copied().unwrap_or_default();
+                let dependencies = requirements
+                    .iter()
+                    .map(|x| (*x, self.type_names.get(x).copied().unwrap_or_default()))
+                    .collect();
+                Binding {
+                    type_id: *type_id,
+                    type_name,
+                    short_name: make_name_shorter(type_name, true),
+                    kind: self
+                        .binding_kinds
+                        .get(type_id)
+                        .copied()
+                        .unwrap_or(BindingKind::Function),
+                    dependencies,
+                    debug_line: self.debug_lines.get(type_id).cloned(),
+                    transient: self.transient_types.contains(type_id),
+                    built: self.static_values.contains_key(type_id),
+                }
+            })
+            .collect()
+    }
+
+    fn graph(&self, short_types: bool) -> Graph {
+        let mut nodes = Vec::new();
+        let mut indexes: HashMap<TypeId, usize> = HashMap::new();
+        let mut node_index = |type_id: TypeId| -> usize {
+            *indexes.entry(type_id).or_insert_with(|| {
+                let type_name = self.type_names.get(&type_id).copied().unwrap_or_default();
+                nodes.push(Node {
+                    name: make_name_shorter(type_name, short_types).to_string(),
+                    kind: self.binding_kinds.get(&type_id).copied(),
+                    debug_line: self.debug_lines.get(&type_id).cloned(),
+                });
+                nodes.len() - 1
+            })
+        };
+
+        let mut edges = Vec::new();
+        for (type_id, requirements) in self.requirements_graph.iter() {
+            let from = node_index(*type_id);
+            for dependency in requirements {
+                edges.push(Edge {
+                    from,
+                    to: node_index(*dependency),
+                    lazy: self.lazy_types.contains(type_id),
+                    optional: self.optional_requirements.contains(&(*type_id, *dependency)),
+                });
+            }
+        }
+        Graph { nodes, edges }
+    }
+
+    fn type_info(&self, type_id: TypeId) -> TypeInfo {
+        TypeInfo {
+            type_id,
+            type_name: self.type_names.get(&type_id).copied().unwrap_or_default(),
+            debug_line: self.debug_lines.get(&type_id).cloned(),
+        }
+    }
+
+    fn types_info(&self, type_ids: impl IntoIterator<Item = TypeId>) -> Vec<TypeInfo> {
+        type_ids.into_iter().map(|x| self.type_info(x)).collect()
     }
 
     fn verify_missing_deps(
         &self,
         additional_types: &HashSet<TypeId>,
         short_types: bool,
-    ) -> anyhow::Result<()> {
+    ) -> Result<()> {
         let available_types: HashSet<_> = additional_types
             .iter()
             .chain(self.requirements_graph.iter().map(|(id, _)| id))
             .collect();
-        let missing_local_types: Vec<(TypeId, &str, Vec<&str>)> = self
+        let dependencies: Vec<MissingDependencies> = self
             .requirements_graph
             .iter()
             .flat_map(|(type_id, requirements)| {
                 let missing_requirements: Vec<_> = requirements
                     .iter()
                     .filter(|x| !available_types.contains(x))
-                    .flat_map(|x| self.type_names.get(x).cloned())
+                    .filter(|x| !self.optional_requirements.contains(&(*type_id, **x)))
+                    .map(|x| self.type_info(*x))
                     .collect();
 
                 if missing_requirements.is_empty() {
                     None
                 } else {
-                    self.type_names
-                        .get(type_id)
-                        .map(|&name| (*type_id, name, missing_requirements))
+                    Some(MissingDependencies {
+                        dependent: self.type_info(*type_id),
+                        missing: missing_requirements,
+                    })
                 }
             })
             .collect();
 
-        if missing_local_types.is_empty() {
+        if dependencies.is_empty() {
             return Ok(());
         }
-        // TODO move to func
-        let mut message = String::new();
-        message.push_str("Missing injection values:\n");
-        for (type_id, value, requirements) in missing_local_types {
-            message.push_str("for type ");
-            message.push_str(make_name_shorter(value, short_types));
-            if let Some(x) = self.debug_lines.get(&type_id) {
-                message.push_str("\n at ");
-                message.push_str(x.as_str());
+        Err(Error::MissingDependencies {
+            dependencies,
+            short_types,
+        })
+    }
+
+    fn named_type_info(&self, (name, type_id): &NamedKey) -> NamedTypeInfo {
+        NamedTypeInfo {
+            name: name.clone(),
+            type_info: self.type_info(*type_id),
+        }
+    }
+
+    fn verify_named_duplicates(&self, short_types: bool) -> Result<()> {
+        if self.named_duplicates.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::NamedDuplicates {
+            names: self
+                .named_duplicates
+                .iter()
+                .map(|x| self.named_type_info(x))
+                .collect(),
+            short_types,
+        })
+    }
+
+    fn verify_missing_named_deps(
+        &self,
+        additional_named: &HashSet<NamedKey>,
+        short_types: bool,
+    ) -> Result<()> {
+        let mut dependencies: Vec<MissingNamedDependencies> = Vec::new();
+        for (type_id, key) in self.named_requirements.iter() {
+            if self.named_values.contains_key(key) || additional_named.contains(key) {
+                continue;
             }
-            message.push_str("\n");
-            message.push_str("missing dependencies: ");
-            message.push_str(make_name_shorter(&requirements[0], short_types));
-            for r in requirements.iter().skip(1) {
-                message.push_str(", ");
-                message.push_str(make_name_shorter(r.trim(), short_types));
+            let missing = self.named_type_info(key);
+            match dependencies.iter_mut().find(|x| x.dependent.type_id == *type_id) {
+                Some(dependency) => dependency.missing.push(missing),
+                None => dependencies.push(MissingNamedDependencies {
+                    dependent: self.type_info(*type_id),
+                    missing: vec![missing],
+                }),
             }
-            message.push_str("\n\n");
+        }
+        if dependencies.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::MissingNamedDependencies {
+            dependencies,
+            short_types,
+        })
+    }
+
+    fn verify_overrides(&self, short_types: bool) -> Result<()> {
+        if self.missing_overrides.is_empty() && self.missing_named_overrides.is_empty() {
+            return Ok(());
         }
 
-        let err = anyhow::Error::msg(message);
-        Err(err)
+        Err(Error::MissingOverrides {
+            types: self.types_info(self.missing_overrides.iter().copied()),
+            names: self
+                .missing_named_overrides
+                .iter()
+                .map(|x| self.named_type_info(x))
+                .collect(),
+            short_types,
+        })
     }
 
-    fn verify_nested_lazy_deps(&self, short_types: bool) -> anyhow::Result<()> {
+    fn verify_nested_lazy_deps(&self, short_types: bool) -> Result<()> {
         let invalid_lazy_types: Vec<_> = self
             .requirements_graph
             .iter()
@@ -229,37 +765,49 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
             return Ok(());
         }
 
-        let recursive_names = invalid_lazy_types
-            .into_iter()
-            .flat_map(|x| self.type_names.get(&x))
-            .map(|name| make_name_shorter(name, short_types));
-        let recursive_names = join(recursive_names, ", ");
-        let message = format!("Nested lazy dependencies: {recursive_names}");
-        let err = anyhow::Error::msg(message);
-        Err(err)
+        Err(Error::NestedLazy {
+            types: self.types_info(invalid_lazy_types),
+            short_types,
+        })
+    }
+
+    fn verify_transient_lifecycle(&self, short_types: bool) -> Result<()> {
+        let transient_types: Vec<_> = self
+            .lifecycle_hooks
+            .iter()
+            .map(|x| x.type_id)
+            .filter(|x| self.transient_types.contains(x))
+            .collect();
+
+        if transient_types.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::TransientLifecycle {
+            types: self.types_info(transient_types),
+            short_types,
+        })
     }
 
     fn verify_recursive_deps(
         &self,
         additional_types: &HashSet<TypeId>,
         short_types: bool,
-    ) -> anyhow::Result<()> {
-        self.traverse_dependencies_and_verify_recursion(
-            additional_types,
-            short_types,
-            |_| Ok(()),
-            "Dependencies cycle (one or more) found :",
-        )
+    ) -> Result<()> {
+        self.traverse_dependencies_and_verify_recursion(additional_types, short_types)?;
+        Ok(())
     }
 
+    // Returns types grouped by layers.
+    // All dependencies of types from a layer are resolved in the previous layers,
+    // so types inside a single layer are independent of each other.
     fn traverse_dependencies_and_verify_recursion(
         &self,
         additional_types: &HashSet<TypeId>,
         short_types: bool,
-        mut on_resolve: impl FnMut(TypeId) -> anyhow::Result<()>,
-        err_message: &'static str,
-    ) -> anyhow::Result<()> {
+    ) -> Result<Vec<Vec<TypeId>>> {
         let mut available_types: HashSet<_> = additional_types.iter().cloned().collect();
+        let mut layers = Vec::new();
 
         let resolved_lazy_types: HashSet<_> = self
             .requirements_graph
@@ -270,9 +818,9 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
             .collect();
 
         // Let's start by initializing lazy dependencies
-        for lazy_type in resolved_lazy_types.iter().cloned() {
-            available_types.insert(lazy_type);
-            on_resolve(lazy_type)?;
+        if !resolved_lazy_types.is_empty() {
+            available_types.extend(resolved_lazy_types.iter().cloned());
+            layers.push(resolved_lazy_types.iter().cloned().collect());
         }
 
         // initializing lazy types at the very beginning
@@ -283,22 +831,38 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
             .filter(|(k, _)| !resolved_lazy_types.contains(k))
             .collect();
 
+        // optional dependencies which are not bound are skipped
+        let bound_types: HashSet<_> = additional_types
+            .iter()
+            .chain(self.requirements_graph.iter().map(|(type_id, _)| type_id))
+            .cloned()
+            .collect();
+        let is_skipped = |type_id: &TypeId, dependency: &TypeId| {
+            !bound_types.contains(dependency)
+                && self.optional_requirements.contains(&(*type_id, *dependency))
+        };
+
         let mut current_len = left_deps.len();
         loop {
+            if left_deps.is_empty() {
+                return Ok(layers);
+            }
             // Resolving dependencies, at best works in O(n) time complexity, at worst in O(n^2)
             // In the most typical case, when DI represents an architecture
             // in the form of layers, it works roughly in O(n*k) time complexity, where k is the number of layers in the dependencies
             // and n is the number of dependencies.
             let resolved: Vec<TypeId> = left_deps
                 .iter()
-                .filter(|(_, deps)| deps.iter().all(|x| available_types.contains(x)))
-                .map(|(type_id, _)| type_id.clone())
+                .filter(|(type_id, deps)| {
+                    deps.iter()
+                        .all(|x| available_types.contains(x) || is_skipped(type_id, x))
+                })
+                .map(|(type_id, _)| *type_id)
                 .collect();
 
-            for type_id in resolved {
-                left_deps.remove(&type_id);
-                available_types.insert(type_id);
-                on_resolve(type_id)?;
+            for type_id in resolved.iter() {
+                left_deps.remove(type_id);
+                available_types.insert(*type_id);
             }
 
             // cycle found
@@ -307,97 +871,455 @@ impl<LastType: Clone + 'static> InjectionBinder<LastType> {
                 break;
             }
             current_len = left_deps.len();
-            if left_deps.is_empty() {
-                return Ok(());
-            }
+            layers.push(resolved);
         }
-        let cycle_type_names = left_deps
+        // types which merely depend on a cycle are not reported
+        let unresolved_graph: HashMap<TypeId, Vec<TypeId>> = left_deps
             .iter()
-            .flat_map(|(type_id, _)| self.type_names.get(&type_id))
-            .map(|name| make_name_shorter(name, short_types));
-        let cycle_type_names = join(cycle_type_names, ", ");
-        let err = anyhow::anyhow!("{err_message}{cycle_type_names}");
-        Err(err)
+            .map(|(type_id, deps)| {
+                let deps = deps.iter().filter(|x| left_deps.contains_key(x)).copied();
+                (*type_id, deps.collect())
+            })
+            .collect();
+        let order: Vec<_> = self.requirements_graph.iter().map(|(x, _)| *x).collect();
+        let cycles = find_cycles(&unresolved_graph, &order)
+            .into_iter()
+            .map(|path| Cycle {
+                path: self.types_info(path),
+            })
+            .collect();
+        Err(Error::Cycle {
+            cycles,
+            short_types,
+        })
     }
 
+    // Every type must be bound only once, including instances.
+    // Intentional replacements should be done with `override_instance`/`override_binder`.
     fn verify_duplicates(
         &self,
         additional_types: &HashSet<TypeId>,
         short_types: bool,
-    ) -> anyhow::Result<()> {
+    ) -> Result<()> {
         let mut available: HashSet<_> = additional_types.clone();
-        let mut duplicates: HashSet<TypeId> = Default::default();
-        for (type_id, requirements) in &self.requirements_graph {
-            if requirements.is_empty() {
-                continue;
-            }
-            if !available.insert(*type_id) {
-                duplicates.insert(*type_id);
+        let mut duplicates: Vec<TypeId> = Default::default();
+        for (type_id, _) in &self.requirements_graph {
+            if !available.insert(*type_id) && !duplicates.contains(type_id) {
+                duplicates.push(*type_id);
             }
         }
         if duplicates.is_empty() {
             return Ok(());
         }
 
-        let duplicates_names = duplicates
-            .iter()
-            .flat_map(|type_id| self.type_names.get(type_id))
-            .map(|name| make_name_shorter(name, short_types));
-        let duplicates_names = join(duplicates_names, ", ");
-        let err = anyhow::anyhow!("Dependencies duplications found: {duplicates_names}");
-        Err(err)
+        let duplicates = duplicates
+            .into_iter()
+            .map(|type_id| Duplicate {
+                type_info: self.type_info(type_id),
+                binding_sites: self
+                    .binding_sites
+                    .get(&type_id)
+                    .into_iter()
+                    .flatten()
+                    .map(|site| site.to_string())
+                    .collect(),
+            })
+            .collect();
+        Err(Error::Duplicates {
+            duplicates,
+            short_types,
+        })
     }
 
-    pub fn verify(
+    pub fn verify(&self, additional_types: HashSet<TypeId>, short_types: bool) -> Result<()> {
+        self.verify_with_named(additional_types, &HashSet::new(), short_types)
+    }
+
+    // `additional_named` are named values available from outside, for example from the parent injector
+    fn verify_with_named(
         &self,
         additional_types: HashSet<TypeId>,
+        additional_named: &HashSet<NamedKey>,
         short_types: bool,
-    ) -> anyhow::Result<()> {
+    ) -> Result<()> {
+        let result = self.verify_graph(additional_types, additional_named, short_types);
+        if let Err(err) = &result {
+            trace::verification_failed(err);
+        }
+        result
+    }
+
+    fn verify_graph(
+        &self,
+        additional_types: HashSet<TypeId>,
+        additional_named: &HashSet<NamedKey>,
+        short_types: bool,
+    ) -> Result<()> {
         let additional_deps: HashSet<_> = additional_types
-            .into_iter()
-            .chain(self.static_values.iter().map(|(type_id, _)| *type_id))
+            .iter()
+            .copied()
+            .chain(self.static_values.keys().copied())
             .collect();
 
-        self.verify_duplicates(&additional_deps, short_types)?;
+        self.verify_overrides(short_types)?;
+        self.verify_duplicates(&additional_types, short_types)?;
+        self.verify_named_duplicates(short_types)?;
         self.verify_nested_lazy_deps(short_types)?;
+        self.verify_transient_lifecycle(short_types)?;
         self.verify_missing_deps(&additional_deps, short_types)?;
+        self.verify_missing_named_deps(additional_named, short_types)?;
         // Order is important, as the recursion check will also find fields where dependencies are missing
         self.verify_recursive_deps(&additional_deps, short_types)?;
 
         Ok(())
     }
 
-    pub fn build(mut self) -> anyhow::Result<Injector> {
-        let initial_known_deps: HashSet<_> = {
-            self.static_values
-                .iter()
-                .map(|(type_id, _)| *type_id)
-                .collect()
+    pub fn build(self) -> Result<Injector> {
+        self.build_with_parent(None)
+    }
+
+    // Values of the parent are available for the built injector, but not vice versa
+    pub(crate) fn build_with_parent(self, parent: Option<&Injector>) -> Result<Injector> {
+        self.build_and_report(parent, None)
+    }
+
+    // Builds like `build` and measures every builder, for example to find slow components
+    pub fn build_with_report(self) -> Result<(Injector, BuildReport)> {
+        let mut report = BuildReport::default();
+        let injector = self.build_and_report(None, Some(&mut report))?;
+        Ok((injector, report))
+    }
+
+    fn build_and_report(
+        mut self,
+        parent: Option<&Injector>,
+        mut report: Option<&mut BuildReport>,
+    ) -> Result<Injector> {
+        self.verify_no_async_builders()?;
+        // Own bindings shadow the parent ones, so dependents are built with the child values
+        let own_types: HashSet<_> = self.requirements_graph.iter().map(|(x, _)| *x).collect();
+        let parent_types = parent
+            .map(|x| x.type_ids())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| !own_types.contains(x))
+            .collect();
+        let parent_named = parent
+            .map(|x| x.named_keys())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|x| !self.named_values.contains_key(x))
+            .collect();
+        let layers = self.verify_and_split_by_layers(parent_types, &parent_named)?;
+
+        let injector = self.take_injector(&layers);
+        let injector = match parent {
+            Some(parent) => injector.with_parent(parent.clone()),
+            None => injector,
         };
+        let builders_map = self.take_builders();
+        let mut requirements: HashMap<TypeId, Vec<TypeId>> = HashMap::new();
+        if report.is_some() {
+            for (type_id, dependencies) in self.requirements_graph.iter() {
+                requirements.entry(*type_id).or_default().extend(dependencies);
+            }
+        }
 
-        self.verify(initial_known_deps.clone(), false)?;
+        let build_start = Instant::now();
+        for (layer, type_ids) in layers.into_iter().enumerate() {
+            for type_id in type_ids {
+                let Some(builder) = builders_map.get(&type_id) else {
+                    continue;
+                };
+                let start = Instant::now();
+                let item = self.run_builder(type_id, builder, &injector)?;
+                let duration = start.elapsed();
+                if let Some(report) = report.as_deref_mut() {
+                    let order = report.entries.len();
+                    let dependencies = requirements.get(&type_id);
+                    let entry = self.report_entry(type_id, order, layer, start - build_start, duration, dependencies);
+                    report.entries.push(entry);
+                }
+                injector.insert(type_id, item);
+            }
+        }
+        Ok(injector)
+    }
+
+    fn report_entry(
+        &self,
+        type_id: TypeId,
+        order: usize,
+        layer: usize,
+        start: Duration,
+        duration: Duration,
+        dependencies: Option<&Vec<TypeId>>,
+    ) -> BuildReportEntry {
+        let dependencies = dependencies
+            .into_iter()
+            .flatten()
+            .map(|x| (*x, self.type_names.get(x).copied().unwrap_or_default()))
+            .collect();
+        BuildReportEntry {
+            type_id,
+            type_name: self.type_names.get(&type_id).copied().unwrap_or_default(),
+            order,
+            layer,
+            start,
+            duration,
+            dependencies,
+        }
+    }
 
-        let injector = Injector::new(mem::take(&mut self.static_values));
+    // Builds only the root and its transitive dependencies and returns the root.
+    // Other bindings are neither verified nor built, but overrides of types that were never bound
+    // are still reported, as they are usually typos.
+    pub fn build_root<Root: Clone + 'static>(mut self) -> Result<Root> {
+        let reachable = self.reachable_types(TypeId::of::<Root>());
+        self.static_values.retain(|type_id, _| reachable.contains(type_id));
+        self.builders.retain(|(type_id, _)| reachable.contains(type_id));
+        self.async_builders.retain(|(type_id, _)| reachable.contains(type_id));
+        self.multibindings.retain(|type_id, _| reachable.contains(type_id));
+        self.requirements_graph.retain(|(type_id, _)| reachable.contains(type_id));
+        self.named_requirements.retain(|(type_id, _)| reachable.contains(type_id));
+        let reachable_named: HashSet<_> = self.named_requirements.iter().map(|(_, key)| key.clone()).collect();
+        self.named_values.retain(|key, _| reachable_named.contains(key));
+        self.named_duplicates.retain(|key| reachable_named.contains(key));
+        self.lifecycle_hooks.retain(|hooks| reachable.contains(&hooks.type_id));
 
-        let builders_map: HashMap<_, _> = mem::take(&mut self.builders).into_iter().collect();
+        self.build()?.get::<Root>()
+    }
 
-        self.traverse_dependencies_and_verify_recursion(
-            &initial_known_deps,
-            false,
-            |type_id| {
+    // Reports bindings which are not used by the root, neither directly nor transitively,
+    // for example wiring left over after refactoring
+    pub fn verify_unused<Root: 'static>(&self, short_types: bool) -> Result<()> {
+        let reachable = self.reachable_types(TypeId::of::<Root>());
+        let mut unused: Vec<TypeId> = Vec::new();
+        for (type_id, _) in self.requirements_graph.iter() {
+            if !reachable.contains(type_id) && !unused.contains(type_id) {
+                unused.push(*type_id);
+            }
+        }
+        if unused.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::Unused {
+            types: self.types_info(unused),
+            short_types,
+        })
+    }
+
+    // the type itself and all its transitive dependencies
+    fn reachable_types(&self, root: TypeId) -> HashSet<TypeId> {
+        let mut graph: HashMap<TypeId, Vec<TypeId>> = HashMap::new();
+        for (type_id, requirements) in self.requirements_graph.iter() {
+            graph.entry(*type_id).or_default().extend(requirements);
+        }
+
+        let mut reachable = HashSet::from([root]);
+        let mut queue = vec![root];
+        while let Some(current) = queue.pop() {
+            for dependency in graph.get(&current).into_iter().flatten() {
+                if reachable.insert(*dependency) {
+                    queue.push(*dependency);
+                }
+            }
+        }
+        reachable
+    }
+
+    // Builds the dependencies in the same order as `build`,
+    // but awaits builders from `inject_async_fn`.
+    // Async builders of the same layer are independent and are awaited concurrently.
+    pub async fn build_async(mut self) -> Result<Injector> {
+        let layers = self.verify_and_split_by_layers(HashSet::new(), &HashSet::new())?;
+
+        let injector = self.take_injector(&layers);
+        let builders_map = self.take_builders();
+        let async_builders_map: HashMap<_, _> =
+            mem::take(&mut self.async_builders).into_iter().collect();
+
+        for layer in layers {
+            let mut async_types = Vec::new();
+            let mut futures = Vec::new();
+            for type_id in layer {
                 if let Some(builder) = builders_map.get(&type_id) {
-                    let item = (builder)(&injector)?;
+                    let item = self.run_builder(type_id, builder, &injector)?;
                     injector.insert(type_id, item);
+                } else if let Some(builder) = async_builders_map.get(&type_id) {
+                    async_types.push(type_id);
+                    futures.push(self.builder_span(type_id).instrument((builder)(&injector)));
                 }
-                Ok(())
-            },
-            "Can't resolve dependencies of types :",
-        )?;
+            }
+
+            let items = JoinAll::new(futures).await;
+            for (type_id, item) in async_types.into_iter().zip(items) {
+                let item = item.map_err(|source| Error::BuilderFailed {
+                    type_info: self.type_info(type_id),
+                    source,
+                })?;
+                injector.insert(type_id, item);
+            }
+        }
+        Ok(injector)
+    }
+
+    // Builds the dependencies in the same order as `build`,
+    // but runs builders of each layer on a pool of threads.
+    // If some builders of a layer fail, errors of all of them are reported.
+    #[cfg(not(feature = "local"))]
+    pub fn build_parallel(mut self) -> Result<Injector> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        self.verify_no_async_builders()?;
+        let layers = self.verify_and_split_by_layers(HashSet::new(), &HashSet::new())?;
+
+        let injector = self.take_injector(&layers);
+        let builders_map = self.take_builders();
+        let threads_count = std::thread::available_parallelism()
+            .map(|x| x.get())
+            .unwrap_or(1);
+
+        for layer in layers {
+            let layer_builders: Vec<_> = layer
+                .into_iter()
+                .flat_map(|type_id| {
+                    let builder = builders_map.get(&type_id)?;
+                    Some((type_id, builder, self.builder_span(type_id)))
+                })
+                .collect();
+
+            let next_builder = AtomicUsize::new(0);
+            let workers_count = threads_count.min(layer_builders.len());
+            let results: Vec<_> = std::thread::scope(|scope| {
+                let workers: Vec<_> = (0..workers_count)
+                    .map(|_| {
+                        scope.spawn(|| {
+                            let mut results = Vec::new();
+                            loop {
+                                let idx = next_builder.fetch_add(1, Ordering::Relaxed);
+                                let Some((type_id, builder, span)) = layer_builders.get(idx) else {
+                                    return results;
+                                };
+                                results.push((*type_id, span.in_scope(|| (builder)(&injector))));
+                            }
+                        })
+                    })
+                    .collect();
+                workers
+                    .into_iter()
+                    .flat_map(|worker| match worker.join() {
+                        Ok(results) => results,
+                        Err(panic) => std::panic::resume_unwind(panic),
+                    })
+                    .collect()
+            });
+
+            let mut errors = Vec::new();
+            for (type_id, result) in results {
+                match result {
+                    Ok(item) => injector.insert(type_id, item),
+                    Err(source) => errors.push(Error::BuilderFailed {
+                        type_info: self.type_info(type_id),
+                        source,
+                    }),
+                }
+            }
+            match errors.len() {
+                0 => {}
+                1 => return Err(errors.remove(0)),
+                _ => return Err(Error::Multiple(errors)),
+            }
+        }
         Ok(injector)
     }
+
+    // Creates an injector with static values, transient builders
+    // and lifecycle hooks sorted in the order of construction
+    fn take_injector(&mut self, layers: &[Vec<TypeId>]) -> Injector {
+        let order: HashMap<TypeId, usize> = layers
+            .iter()
+            .flatten()
+            .enumerate()
+            .map(|(idx, type_id)| (*type_id, idx))
+            .collect();
+        let mut lifecycle_hooks = mem::take(&mut self.lifecycle_hooks);
+        lifecycle_hooks.sort_by_key(|hooks| order.get(&hooks.type_id).copied());
+
+        let (transients, builders): (Vec<_>, Vec<_>) = mem::take(&mut self.builders)
+            .into_iter()
+            .partition(|(type_id, _)| self.transient_types.contains(type_id));
+        self.builders = builders;
+
+        let bindings = self.bindings();
+        Injector::new(mem::take(&mut self.static_values))
+            .with_named_values(mem::take(&mut self.named_values))
+            .with_transients(transients.into_iter().collect())
+            .with_lifecycle(lifecycle_hooks)
+            .with_bindings(bindings)
+    }
+
+    fn take_builders(&mut self) -> HashMap<TypeId, Box<Builder>> {
+        let mut builders: HashMap<_, _> = mem::take(&mut self.builders).into_iter().collect();
+        for (type_id, multibinding) in mem::take(&mut self.multibindings) {
+            builders.insert(type_id, multibinding.into_builder());
+        }
+        builders
+    }
+
+    fn verify_and_split_by_layers(
+        &self,
+        additional_types: HashSet<TypeId>,
+        additional_named: &HashSet<NamedKey>,
+    ) -> Result<Vec<Vec<TypeId>>> {
+        self.verify_with_named(additional_types.clone(), additional_named, false)?;
+
+        let initial_known_deps: HashSet<_> = additional_types
+            .into_iter()
+            .chain(self.static_values.keys().copied())
+            .collect();
+
+        self.traverse_dependencies_and_verify_recursion(&initial_known_deps, false)
+    }
+
+    fn run_builder(
+        &self,
+        type_id: TypeId,
+        builder: &Builder,
+        injector: &Injector,
+    ) -> Result<Box<AnyValue>> {
+        self.builder_span(type_id)
+            .in_scope(|| (builder)(injector))
+            .map_err(|source| Error::BuilderFailed {
+                type_info: self.type_info(type_id),
+                source,
+            })
+    }
+
+    fn builder_span(&self, type_id: TypeId) -> BuilderSpan {
+        let type_name = self.type_names.get(&type_id).copied().unwrap_or_default();
+        let dependencies = self
+            .requirements_graph
+            .iter()
+            .filter(move |(id, _)| *id == type_id)
+            .flat_map(|(_, requirements)| requirements)
+            .map(|x| self.type_names.get(x).copied().unwrap_or_default());
+        let debug_line = self.debug_lines.get(&type_id).map(|x| x.as_str());
+        trace::builder_span(type_name, debug_line, dependencies)
+    }
+
+    fn verify_no_async_builders(&self) -> Result<()> {
+        if self.async_builders.is_empty() {
+            return Ok(());
+        }
+        Err(Error::AsyncBuilders {
+            types: self.types_info(self.async_builders.iter().map(|(type_id, _)| *type_id)),
+        })
+    }
 }
 
-fn join<T, IT>(mut iter: IT, separator: &str) -> String
+pub(crate) fn join<T, IT>(mut iter: IT, separator: &str) -> String
 where
     T: std::fmt::Display,
     IT: Iterator<Item = T>,
@@ -417,7 +1339,8 @@ where
     result
 }
 
-fn make_name_shorter(name: &str, short_types: bool) -> &str {
+#[allow(clippy::collapsible_match)]
+pub(crate) fn make_name_shorter(name: &str, short_types: bool) -> &str {
     if short_types {
         let mut openned_generics = 0;
         let mut found_idx = None;
@@ -1,40 +1,204 @@
+use crate::error::{Error, NamedTypeInfo, Result, TypeInfo};
+use crate::graph::Binding;
+use crate::injection_binder::{InjectionBinder, NamedKey};
+use crate::lifecycle::LifecycleHooks;
+use crate::thread_safety::{AnyValue, Builder, Shared};
+use crate::trace;
 use crate::tuples::TupleInjectTypes;
-use anyhow::anyhow;
 use parking_lot::RwLock;
-use std::any::{type_name, Any, TypeId};
-use std::collections::HashMap;
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 #[derive(Default, Clone)]
 pub struct Injector {
-    values: Arc<RwLock<HashMap<TypeId, Box<dyn Any>>>>,
+    values: Arc<RwLock<HashMap<TypeId, Box<AnyValue>>>>,
+    named_values: Shared<HashMap<NamedKey, Box<AnyValue>>>,
+    transients: Shared<HashMap<TypeId, Box<Builder>>>,
+    parent: Option<Shared<Injector>>,
+    // sorted in the order of construction
+    lifecycle: Shared<Vec<LifecycleHooks>>,
+    bindings: Arc<Vec<Binding>>,
 }
 
 impl Injector {
-    pub(crate) fn new(values: HashMap<TypeId, Box<dyn Any + 'static>>) -> Self {
+    pub(crate) fn new(values: HashMap<TypeId, Box<AnyValue>>) -> Self {
         let res = Self::default();
         *res.values.write() = values;
         res
     }
 
-    pub(crate) fn insert(&self, type_id: TypeId, item: Box<dyn Any + 'static>) {
+    pub(crate) fn with_named_values(mut self, named_values: HashMap<NamedKey, Box<AnyValue>>) -> Self {
+        self.named_values = Shared::new(named_values);
+        self
+    }
+
+    pub(crate) fn with_transients(mut self, transients: HashMap<TypeId, Box<Builder>>) -> Self {
+        self.transients = Shared::new(transients);
+        self
+    }
+
+    pub(crate) fn with_parent(mut self, parent: Injector) -> Self {
+        self.parent = Some(Shared::new(parent));
+        self
+    }
+
+    pub(crate) fn with_lifecycle(mut self, lifecycle: Vec<LifecycleHooks>) -> Self {
+        self.lifecycle = Shared::new(lifecycle);
+        self
+    }
+
+    pub(crate) fn with_bindings(mut self, bindings: Vec<Binding>) -> Self {
+        self.bindings = Arc::new(bindings);
+        self
+    }
+
+    pub(crate) fn insert(&self, type_id: TypeId, item: Box<AnyValue>) {
         self.values.write().insert(type_id, item);
     }
 
-    pub fn get<X: Clone + 'static>(&self) -> anyhow::Result<X> {
+    pub fn get<X: Clone + 'static>(&self) -> Result<X> {
         let type_id = TypeId::of::<X>();
-        self.values
+        let value = self
+            .values
             .read()
             .get(&type_id)
             .and_then(|x| x.as_ref().downcast_ref::<X>())
-            .cloned()
-            .ok_or_else(|| {
-                let type_name = type_name::<X>();
-                anyhow!("Missing value of type {type_name}")
+            .cloned();
+        if let Some(value) = value {
+            return Ok(value);
+        }
+
+        // the lock is released here, because transient builders read their dependencies from the injector
+        if let Some(builder) = self.transients.get(&type_id) {
+            let value = (builder)(self).map_err(|source| Error::BuilderFailed {
+                type_info: TypeInfo::of::<X>(),
+                source,
+            })?;
+            if let Ok(value) = value.downcast::<X>() {
+                return Ok(*value);
+            }
+        }
+
+        if let Some(parent) = &self.parent {
+            return parent.get::<X>();
+        }
+
+        let type_info = TypeInfo::of::<X>();
+        trace::missing_value(type_info.type_name);
+        Err(Error::MissingValue { type_info })
+    }
+
+    // Reads a value bound with `InjectionBinder::instance_named`
+    pub fn get_named<X: Clone + 'static>(&self, name: &str) -> Result<X> {
+        let key = (name.to_string(), TypeId::of::<X>());
+        let value = self
+            .named_values
+            .get(&key)
+            .and_then(|x| x.as_ref().downcast_ref::<X>())
+            .cloned();
+        if let Some(value) = value {
+            return Ok(value);
+        }
+
+        if let Some(parent) = &self.parent {
+            return parent.get_named::<X>(name);
+        }
+
+        let type_info = TypeInfo::of::<X>();
+        trace::missing_value(type_info.type_name);
+        Err(Error::MissingNamedValue {
+            named: NamedTypeInfo {
+                name: name.to_string(),
+                type_info,
+            },
+        })
+    }
+
+    // Returns None if there is no value of this type
+    pub fn get_optional<X: Clone + 'static>(&self) -> Result<Option<X>> {
+        if self.contains(TypeId::of::<X>()) {
+            self.get::<X>().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn contains(&self, type_id: TypeId) -> bool {
+        self.values.read().contains_key(&type_id)
+            || self.transients.contains_key(&type_id)
+            || self.parent.iter().any(|parent| parent.contains(type_id))
+    }
+
+    // Creates a child injector, for example for a request or a session.
+    // The child sees all values of this injector, but this injector never sees values of the child.
+    pub fn scope<T: Clone + 'static>(&self, binder: InjectionBinder<T>) -> Result<Injector> {
+        binder.build_with_parent(Some(self))
+    }
+
+    // types of all values available in this injector including parents
+    pub(crate) fn type_ids(&self) -> HashSet<TypeId> {
+        let mut result: HashSet<TypeId> = self.values.read().keys().copied().collect();
+        result.extend(self.transients.keys().copied());
+        if let Some(parent) = &self.parent {
+            result.extend(parent.type_ids());
+        }
+        result
+    }
+
+    // Types bound in this injector, bindings of the parent are available from the parent
+    pub fn bindings(&self) -> Vec<Binding> {
+        let values = self.values.read();
+        self.bindings
+            .iter()
+            .map(|binding| Binding {
+                built: values.contains_key(&binding.type_id),
+                ..binding.clone()
             })
+            .collect()
+    }
+
+    // names and types of all named values available in this injector including parents
+    pub(crate) fn named_keys(&self) -> HashSet<NamedKey> {
+        let mut result: HashSet<NamedKey> = self.named_values.keys().cloned().collect();
+        if let Some(parent) = &self.parent {
+            result.extend(parent.named_keys());
+        }
+        result
     }
 
-    pub fn get_tuple<Tuple: TupleInjectTypes>(&self) -> anyhow::Result<Tuple> {
+    pub fn get_tuple<Tuple: TupleInjectTypes>(&self) -> Result<Tuple> {
         Tuple::read_from_injector(self)
     }
+
+    // Starts components in the order of their dependencies.
+    // If some component fails to start, already started components are stopped in reverse order.
+    pub fn start_all(&self) -> Result<()> {
+        for (idx, hooks) in self.lifecycle.iter().enumerate() {
+            if let Err(err) = hooks.start(self) {
+                let _ = self.stop_hooks(&self.lifecycle[..idx]);
+                return Err(err);
+            }
+        }
+        Ok(())
+    }
+
+    // Stops components in the reverse order of their dependencies.
+    // All components are stopped even if some of them fail.
+    pub fn stop_all(&self) -> Result<()> {
+        self.stop_hooks(&self.lifecycle)
+    }
+
+    fn stop_hooks(&self, hooks: &[LifecycleHooks]) -> Result<()> {
+        let mut errors: Vec<_> = hooks
+            .iter()
+            .rev()
+            .flat_map(|hooks| hooks.stop(self).err())
+            .collect();
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(Error::Multiple(errors)),
+        }
+    }
 }
@@ -0,0 +1,49 @@
+use crate::thread_safety::BoxFuture;
+use std::future::Future;
+use std::mem;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+// Minimal runtime agnostic analog of `futures::future::join_all`.
+// Polls all futures on every wake up, which is fine for a small number of builders in a layer.
+pub(crate) struct JoinAll<T> {
+    futures: Vec<Option<BoxFuture<T>>>,
+    results: Vec<Option<T>>,
+}
+
+impl<T> JoinAll<T> {
+    pub(crate) fn new(futures: Vec<BoxFuture<T>>) -> Self {
+        let results = futures.iter().map(|_| None).collect();
+        let futures = futures.into_iter().map(Some).collect();
+        Self { futures, results }
+    }
+}
+
+impl<T: Unpin> Future for JoinAll<T> {
+    type Output = Vec<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let mut all_ready = true;
+        for (future_slot, result_slot) in this.futures.iter_mut().zip(this.results.iter_mut()) {
+            if let Some(future) = future_slot {
+                match future.as_mut().poll(cx) {
+                    Poll::Ready(result) => {
+                        *result_slot = Some(result);
+                        *future_slot = None;
+                    }
+                    Poll::Pending => all_ready = false,
+                }
+            }
+        }
+
+        if !all_ready {
+            return Poll::Pending;
+        }
+        let results = mem::take(&mut this.results)
+            .into_iter()
+            .flatten()
+            .collect();
+        Poll::Ready(results)
+    }
+}
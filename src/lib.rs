@@ -1,23 +1,43 @@
 pub mod component_meta;
+mod cycles;
+pub mod default_value;
+pub mod error;
 pub mod expander;
+pub mod graph;
+pub mod injected_fn;
 pub mod injection_binder;
 pub mod injector;
+mod join;
+pub mod lifecycle;
+pub mod module;
+mod multibinding;
+pub mod provider;
+pub mod report;
 pub mod tags;
+pub mod thread_safety;
+mod trace;
 mod tuples;
+pub mod variant_field;
 
 pub use mydi_macros::Component;
 pub use mydi_macros::ComponentExpander;
+pub use mydi_macros::module;
+pub use error::Error;
+pub use graph::{Binding, BindingKind};
+pub use lifecycle::Lifecycle;
+pub use provider::{Factory, FactoryFn, Provider};
+pub use report::BuildReport;
 
 pub type Injector = injector::Injector;
 pub type InjectionBinder<T> = injection_binder::InjectionBinder<T>;
 
-pub type Lazy<T> = std::sync::Arc<once_cell::sync::Lazy<T, Box<dyn FnOnce() -> T>>>;
+pub type Lazy<T> = std::sync::Arc<once_cell::sync::Lazy<T, thread_safety::LazyInit<T>>>;
 
 impl<T: Clone + 'static> crate::component_meta::ComponentMeta for Lazy<T> {
     fn inject(injector: &crate::injector::Injector) -> anyhow::Result<Self> {
         use std::sync::Arc;
         let injector = injector.clone();
-        let func: Box<dyn FnOnce() -> T + 'static> = Box::new(move || -> T {
+        let func: thread_safety::LazyInit<T> = Box::new(move || -> T {
             match injector.get::<T>() {
                 Ok(x) => x,
                 _ => unreachable!(),
@@ -40,6 +60,10 @@ impl<T: Clone + 'static> crate::component_meta::ComponentMeta for Lazy<T> {
     }
 }
 
+impl<T> crate::component_meta::StaticDependencies for Lazy<T> {
+    type Dependencies = (T, ());
+}
+
 #[macro_export]
 macro_rules! erase {
     ( $pointer:ident < $dyn_type:ty > ) => {{
@@ -0,0 +1,57 @@
+use crate::error::{Error, Result, TypeInfo};
+use crate::injector::Injector;
+use crate::thread_safety::Hook;
+use std::any::TypeId;
+
+// Components with start/stop hooks, for example HTTP servers, consumers or pools.
+// `Injector::start_all` starts them in dependency order and `Injector::stop_all` stops them in reverse order.
+pub trait Lifecycle {
+    fn start(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    fn stop(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+// Hooks of a bound type, which read the built value from the injector
+pub struct LifecycleHooks {
+    pub(crate) type_id: TypeId,
+    pub(crate) type_name: &'static str,
+    pub(crate) start: Box<Hook>,
+    pub(crate) stop: Box<Hook>,
+}
+
+impl LifecycleHooks {
+    pub fn of<T: Lifecycle + Clone + 'static>() -> Self {
+        let start: Box<Hook> = Box::new(|injector: &Injector| injector.get::<T>()?.start());
+        let stop: Box<Hook> = Box::new(|injector: &Injector| injector.get::<T>()?.stop());
+        LifecycleHooks {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            start,
+            stop,
+        }
+    }
+
+    pub(crate) fn start(&self, injector: &Injector) -> Result<()> {
+        (self.start)(injector).map_err(|source| self.error("start", source))
+    }
+
+    pub(crate) fn stop(&self, injector: &Injector) -> Result<()> {
+        (self.stop)(injector).map_err(|source| self.error("stop", source))
+    }
+
+    fn error(&self, hook: &'static str, source: anyhow::Error) -> Error {
+        Error::LifecycleFailed {
+            type_info: TypeInfo {
+                type_id: self.type_id,
+                type_name: self.type_name,
+                debug_line: None,
+            },
+            hook,
+            source,
+        }
+    }
+}
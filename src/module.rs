@@ -0,0 +1,25 @@
+// Compile time verification of the wiring declared with `mydi::module!`.
+// The macro creates a marker type and implements `Provides<T>` for every bound type,
+// so duplicate bindings are reported as conflicting implementations
+// and missing dependencies are reported as unsatisfied `ProvidesAll` bounds.
+
+#[diagnostic::on_unimplemented(
+    message = "`{T}` is not bound in the module",
+    label = "missing binding of `{T}`",
+    note = "bind it with `instance`, `inject` or declare it as `external` if it is provided by another binder"
+)]
+pub trait Provides<T> {}
+
+// Dependencies are nested pairs, see `StaticDependencies`
+#[diagnostic::on_unimplemented(
+    message = "some dependencies of the component are not bound in the module",
+    note = "bind them with `instance`, `inject` or declare them as `external` if they are provided by another binder"
+)]
+pub trait ProvidesAll<Dependencies> {}
+
+impl<M> ProvidesAll<()> for M {}
+
+impl<M, Head, Tail> ProvidesAll<(Head, Tail)> for M where M: Provides<Head> + ProvidesAll<Tail> {}
+
+// Used by the generated code to check the bounds without calling anything at runtime
+pub fn verify<Module: ProvidesAll<Dependencies>, Dependencies>() {}
@@ -0,0 +1,81 @@
+use crate::injector::Injector;
+use crate::thread_safety::{AnyValue, Builder, ThreadSafety};
+use std::any::type_name;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+type Collect = fn(Vec<Box<AnyValue>>) -> anyhow::Result<Box<AnyValue>>;
+
+// Several bindings of a single collection type, for example all health checks or all HTTP routes.
+// Each contribution builds an item of the collection, `collect` builds the collection itself.
+pub(crate) struct Multibinding {
+    contributions: Vec<Box<Builder>>,
+    collect: Collect,
+}
+
+impl Multibinding {
+    pub(crate) fn set<T: ThreadSafety + 'static>() -> Self {
+        Self {
+            contributions: Vec::new(),
+            collect: collect_set::<T>,
+        }
+    }
+
+    pub(crate) fn map<K: Eq + Hash + ThreadSafety + 'static, V: ThreadSafety + 'static>() -> Self {
+        Self {
+            contributions: Vec::new(),
+            collect: collect_map::<K, V>,
+        }
+    }
+
+    pub(crate) fn add(&mut self, contribution: Box<Builder>) {
+        self.contributions.push(contribution);
+    }
+
+    pub(crate) fn merge(&mut self, other: Multibinding) {
+        self.contributions.extend(other.contributions);
+    }
+
+    pub(crate) fn into_builder(self) -> Box<Builder> {
+        let Multibinding {
+            contributions,
+            collect,
+        } = self;
+        Box::new(move |injector: &Injector| {
+            let items = contributions
+                .iter()
+                .map(|contribution| (contribution)(injector))
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            (collect)(items)
+        })
+    }
+}
+
+fn collect_set<T: ThreadSafety + 'static>(items: Vec<Box<AnyValue>>) -> anyhow::Result<Box<AnyValue>> {
+    let items = items
+        .into_iter()
+        .map(|item| downcast::<T>(item))
+        .collect::<anyhow::Result<Vec<T>>>()?;
+    Ok(Box::new(items))
+}
+
+fn collect_map<K: Eq + Hash + ThreadSafety + 'static, V: ThreadSafety + 'static>(
+    items: Vec<Box<AnyValue>>,
+) -> anyhow::Result<Box<AnyValue>> {
+    let mut result: HashMap<K, V> = HashMap::with_capacity(items.len());
+    for item in items {
+        let (key, value) = downcast::<(K, V)>(item)?;
+        if result.insert(key, value).is_some() {
+            let map_name = type_name::<HashMap<K, V>>();
+            return Err(anyhow::anyhow!("Duplicate keys found in {map_name}"));
+        }
+    }
+    Ok(Box::new(result))
+}
+
+fn downcast<T: 'static>(item: Box<AnyValue>) -> anyhow::Result<T> {
+    match item.downcast::<T>() {
+        Ok(item) => Ok(*item),
+        Err(_) => Err(anyhow::anyhow!("Invalid item type, expected {}", type_name::<T>())),
+    }
+}
@@ -0,0 +1,111 @@
+use crate::component_meta::{ComponentMeta, StaticDependencies};
+use crate::error::{Error, Result, TypeInfo};
+use crate::injector::Injector;
+use crate::tuples::TupleInjectTypes;
+use std::any::TypeId;
+use std::marker::PhantomData;
+
+// Builds a new `T` on every `get`, `T` itself doesn't have to be bound,
+// but its dependencies are verified as if it were
+pub struct Provider<T> {
+    injector: Injector,
+    _phantom: PhantomData<fn() -> T>,
+}
+
+impl<T: ComponentMeta + 'static> Provider<T> {
+    pub fn get(&self) -> Result<T> {
+        T::inject(&self.injector).map_err(|source| Error::BuilderFailed {
+            type_info: TypeInfo::of::<T>(),
+            source,
+        })
+    }
+}
+
+impl<T: ComponentMeta> ComponentMeta for Provider<T> {
+    fn inject(injector: &Injector) -> anyhow::Result<Self> {
+        Ok(Self {
+            injector: injector.clone(),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn debug_line() -> Option<String> {
+        T::debug_line()
+    }
+
+    fn dependencies_names() -> Vec<(TypeId, &'static str)> {
+        T::dependencies_names()
+    }
+
+    fn optional_dependencies_names() -> Vec<(TypeId, &'static str)> {
+        T::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, TypeId, &'static str)> {
+        T::named_dependencies_names()
+    }
+}
+
+impl<T: StaticDependencies> StaticDependencies for Provider<T> {
+    type Dependencies = T::Dependencies;
+}
+
+impl<T> Clone for Provider<T> {
+    fn clone(&self) -> Self {
+        Self {
+            injector: self.injector.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Types built from runtime arguments together with injected dependencies,
+// `Dependencies` is a tuple of injected types: (), (A,), (A, B), ...
+pub trait Factory<Args>: Sized {
+    type Dependencies: TupleInjectTypes;
+
+    fn create(args: Args, dependencies: Self::Dependencies) -> Self;
+}
+
+// Same as `Provider`, but `call` accepts the runtime arguments of `T::create`
+pub struct FactoryFn<Args, T> {
+    injector: Injector,
+    _phantom: PhantomData<fn(Args) -> T>,
+}
+
+impl<Args, T: Factory<Args>> FactoryFn<Args, T> {
+    pub fn call(&self, args: Args) -> Result<T> {
+        let dependencies = T::Dependencies::read_from_injector(&self.injector)?;
+        Ok(T::create(args, dependencies))
+    }
+}
+
+impl<Args, T: Factory<Args>> ComponentMeta for FactoryFn<Args, T> {
+    fn inject(injector: &Injector) -> anyhow::Result<Self> {
+        Ok(Self {
+            injector: injector.clone(),
+            _phantom: PhantomData,
+        })
+    }
+
+    fn debug_line() -> Option<String> {
+        None
+    }
+
+    fn dependencies_names() -> Vec<(TypeId, &'static str)> {
+        T::Dependencies::dependencies_names()
+    }
+}
+
+impl<Args, T: Factory<Args>> StaticDependencies for FactoryFn<Args, T> {
+    type Dependencies = <T::Dependencies as TupleInjectTypes>::Dependencies;
+}
+
+impl<Args, T> Clone for FactoryFn<Args, T> {
+    fn clone(&self) -> Self {
+        Self {
+            injector: self.injector.clone(),
+            _phantom: PhantomData,
+        }
+    }
+}
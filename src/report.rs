@@ -0,0 +1,133 @@
+use crate::injection_binder::{join, make_name_shorter};
+use std::any::TypeId;
+use std::fmt;
+use std::fmt::Write;
+use std::time::Duration;
+
+// Construction of a single type, see `InjectionBinder::build_with_report`
+#[derive(Debug, Clone)]
+pub struct BuildReportEntry {
+    pub type_id: TypeId,
+    pub type_name: &'static str,
+    // index in the order of construction
+    pub order: usize,
+    // types of a layer depend only on types of previous layers
+    pub layer: usize,
+    // time from the start of the build to the start of the builder
+    pub start: Duration,
+    pub duration: Duration,
+    pub dependencies: Vec<(TypeId, &'static str)>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BuildReport {
+    pub entries: Vec<BuildReportEntry>,
+}
+
+impl BuildReport {
+    pub fn total_duration(&self) -> Duration {
+        self.entries.iter().map(|x| x.duration).sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut result = String::new();
+        result.push('[');
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                result.push(',');
+            }
+            let dependencies = entry
+                .dependencies
+                .iter()
+                .map(|(_, name)| format!("\"{}\"", escape_json(name)));
+            write!(
+                result,
+                "{{\"order\":{},\"layer\":{},\"type\":\"{}\",\"start_us\":{:.3},\"duration_us\":{:.3},\"dependencies\":[{}]}}",
+                entry.order,
+                entry.layer,
+                escape_json(entry.type_name),
+                micros(entry.start),
+                micros(entry.duration),
+                join(dependencies, ","),
+            )
+            .unwrap();
+        }
+        result.push(']');
+        result
+    }
+
+    // Trace event format, which can be opened in chrome://tracing or https://ui.perfetto.dev
+    pub fn to_chrome_trace(&self) -> String {
+        let mut result = String::new();
+        result.push_str("{\"traceEvents\":[");
+        for (idx, entry) in self.entries.iter().enumerate() {
+            if idx > 0 {
+                result.push(',');
+            }
+            write!(
+                result,
+                "{{\"name\":\"{}\",\"cat\":\"mydi\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":1,\"args\":{{\"order\":{},\"layer\":{}}}}}",
+                escape_json(make_name_shorter(entry.type_name, true)),
+                micros(entry.start),
+                micros(entry.duration),
+                entry.order,
+                entry.layer,
+            )
+            .unwrap();
+        }
+        result.push_str("]}");
+        result
+    }
+}
+
+// Table with short type names
+impl fmt::Display for BuildReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let names: Vec<_> = self
+            .entries
+            .iter()
+            .map(|x| make_name_shorter(x.type_name, true))
+            .collect();
+        let name_width = names.iter().map(|x| x.len()).max().unwrap_or(0).max(4);
+
+        writeln!(
+            f,
+            "{:<5}  {:<5}  {:>12}  {:<name_width$}  dependencies",
+            "order", "layer", "time", "type"
+        )?;
+        for (entry, name) in self.entries.iter().zip(names) {
+            let dependencies = entry
+                .dependencies
+                .iter()
+                .map(|(_, name)| make_name_shorter(name, true));
+            writeln!(
+                f,
+                "{:<5}  {:<5}  {:>12}  {:<name_width$}  {}",
+                entry.order,
+                entry.layer,
+                format!("{:?}", entry.duration),
+                name,
+                join(dependencies, ", ")
+            )?;
+        }
+        write!(f, "total: {:?}", self.total_duration())
+    }
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1_000_000.0
+}
+
+fn escape_json(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            char if char.is_control() => write!(result, "\\u{:04x}", char as u32).unwrap(),
+            char => result.push(char),
+        }
+    }
+    result
+}
@@ -1,4 +1,4 @@
-use crate::component_meta::ComponentMeta;
+use crate::component_meta::{ComponentMeta, StaticDependencies};
 use crate::injector::Injector;
 use std::any::TypeId;
 use std::marker::PhantomData;
@@ -45,6 +45,18 @@ impl<T: ComponentMeta, Tag> ComponentMeta for Tagged<T, Tag> {
     fn dependencies_names() -> Vec<(TypeId, &'static str)> {
         T::dependencies_names()
     }
+
+    fn optional_dependencies_names() -> Vec<(TypeId, &'static str)> {
+        T::optional_dependencies_names()
+    }
+
+    fn named_dependencies_names() -> Vec<(&'static str, TypeId, &'static str)> {
+        T::named_dependencies_names()
+    }
+}
+
+impl<T: StaticDependencies, Tag> StaticDependencies for Tagged<T, Tag> {
+    type Dependencies = T::Dependencies;
 }
 
 impl<T: Clone, Tag> Clone for Tagged<T, Tag> {
@@ -0,0 +1,76 @@
+use crate::injector::Injector;
+use std::any::Any;
+use std::future::Future;
+use std::pin::Pin;
+
+// Every value stored inside of the injector must be Send + Sync by default,
+// so the injector itself can be moved between threads and tasks.
+// Enabling the `local` feature removes this restriction for single-threaded applications.
+#[cfg(not(feature = "local"))]
+pub trait ThreadSafety: Send + Sync {}
+
+#[cfg(not(feature = "local"))]
+impl<T: Send + Sync + ?Sized> ThreadSafety for T {}
+
+#[cfg(feature = "local")]
+pub trait ThreadSafety {}
+
+#[cfg(feature = "local")]
+impl<T: ?Sized> ThreadSafety for T {}
+
+// Futures of async builders only need to be Send, they are never shared between threads
+#[cfg(not(feature = "local"))]
+pub trait SendSafety: Send {}
+
+#[cfg(not(feature = "local"))]
+impl<T: Send + ?Sized> SendSafety for T {}
+
+#[cfg(feature = "local")]
+pub trait SendSafety {}
+
+#[cfg(feature = "local")]
+impl<T: ?Sized> SendSafety for T {}
+
+#[cfg(not(feature = "local"))]
+pub(crate) type AnyValue = dyn Any + Send + Sync;
+
+#[cfg(feature = "local")]
+pub(crate) type AnyValue = dyn Any;
+
+// Shared parts of the injector, which hold values that may be neither Send nor Sync with the `local` feature
+#[cfg(not(feature = "local"))]
+pub(crate) type Shared<T> = std::sync::Arc<T>;
+
+#[cfg(feature = "local")]
+pub(crate) type Shared<T> = std::rc::Rc<T>;
+
+#[cfg(not(feature = "local"))]
+pub(crate) type Builder = dyn Fn(&Injector) -> anyhow::Result<Box<AnyValue>> + Send + Sync;
+
+#[cfg(feature = "local")]
+pub(crate) type Builder = dyn Fn(&Injector) -> anyhow::Result<Box<AnyValue>>;
+
+#[cfg(not(feature = "local"))]
+pub(crate) type LazyInit<T> = Box<dyn FnOnce() -> T + Send>;
+
+#[cfg(feature = "local")]
+pub(crate) type LazyInit<T> = Box<dyn FnOnce() -> T>;
+
+#[cfg(not(feature = "local"))]
+pub(crate) type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+#[cfg(feature = "local")]
+pub(crate) type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+#[cfg(not(feature = "local"))]
+pub(crate) type AsyncBuilder =
+    dyn Fn(&Injector) -> BoxFuture<anyhow::Result<Box<AnyValue>>> + Send + Sync;
+
+#[cfg(feature = "local")]
+pub(crate) type AsyncBuilder = dyn Fn(&Injector) -> BoxFuture<anyhow::Result<Box<AnyValue>>>;
+
+#[cfg(not(feature = "local"))]
+pub(crate) type Hook = dyn Fn(&Injector) -> anyhow::Result<()> + Send + Sync;
+
+#[cfg(feature = "local")]
+pub(crate) type Hook = dyn Fn(&Injector) -> anyhow::Result<()>;
@@ -0,0 +1,77 @@
+// Spans and events of the `tracing` feature.
+// Without the feature all functions are no-ops, so the binder code doesn't need cfg attributes.
+use crate::error::Error;
+use crate::thread_safety::BoxFuture;
+
+#[cfg(feature = "tracing")]
+#[derive(Clone)]
+pub(crate) struct BuilderSpan(tracing::Span);
+
+#[cfg(not(feature = "tracing"))]
+#[derive(Clone)]
+pub(crate) struct BuilderSpan;
+
+#[cfg(feature = "tracing")]
+pub(crate) fn builder_span<'a>(
+    type_name: &'static str,
+    debug_line: Option<&str>,
+    dependencies: impl Iterator<Item = &'a str>,
+) -> BuilderSpan {
+    let dependencies = crate::injection_binder::join(dependencies, ", ");
+    BuilderSpan(tracing::info_span!(
+        "mydi_build",
+        type_name,
+        debug_line,
+        dependencies = dependencies.as_str()
+    ))
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn builder_span<'a>(
+    _type_name: &'static str,
+    _debug_line: Option<&str>,
+    _dependencies: impl Iterator<Item = &'a str>,
+) -> BuilderSpan {
+    BuilderSpan
+}
+
+impl BuilderSpan {
+    pub(crate) fn in_scope<T>(&self, f: impl FnOnce() -> anyhow::Result<T>) -> anyhow::Result<T> {
+        #[cfg(feature = "tracing")]
+        {
+            self.0.in_scope(|| {
+                let result = f();
+                if let Err(err) = &result {
+                    tracing::error!(error = %format!("{err:#}"), "builder failed");
+                }
+                result
+            })
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            f()
+        }
+    }
+
+    pub(crate) fn instrument<T: 'static>(&self, future: BoxFuture<T>) -> BoxFuture<T> {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+            Box::pin(future.instrument(self.0.clone()))
+        }
+        #[cfg(not(feature = "tracing"))]
+        {
+            future
+        }
+    }
+}
+
+pub(crate) fn verification_failed(_err: &Error) {
+    #[cfg(feature = "tracing")]
+    tracing::error!(error = %_err, "dependencies verification failed");
+}
+
+pub(crate) fn missing_value(_type_name: &'static str) {
+    #[cfg(feature = "tracing")]
+    tracing::warn!(type_name = _type_name, "missing value");
+}
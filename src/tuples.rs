@@ -1,3 +1,4 @@
+use crate::error::Result;
 use crate::injector::Injector;
 use std::any::{type_name, TypeId};
 
@@ -5,12 +6,17 @@ pub trait TupleInjectTypes
 where
     Self: Sized,
 {
-    fn read_from_injector(injector: &Injector) -> anyhow::Result<Self>;
+    // the same types as nested pairs, see `StaticDependencies`
+    type Dependencies;
+
+    fn read_from_injector(injector: &Injector) -> Result<Self>;
     fn dependencies_names() -> Vec<(TypeId, &'static str)>;
 }
 
 impl TupleInjectTypes for () {
-    fn read_from_injector(_: &Injector) -> anyhow::Result<Self> {
+    type Dependencies = ();
+
+    fn read_from_injector(_: &Injector) -> Result<Self> {
         Ok(())
     }
 
@@ -20,7 +26,9 @@ impl TupleInjectTypes for () {
 }
 
 impl<Arg1: std::clone::Clone + 'static> TupleInjectTypes for (Arg1,) {
-    fn read_from_injector(injector: &Injector) -> anyhow::Result<Self> {
+    type Dependencies = (Arg1, ());
+
+    fn read_from_injector(injector: &Injector) -> Result<Self> {
         Ok((injector.get::<Arg1>()?,))
     }
 
@@ -29,10 +37,18 @@ impl<Arg1: std::clone::Clone + 'static> TupleInjectTypes for (Arg1,) {
     }
 }
 
+// (A, B) -> (A, (B, ()))
+macro_rules! nested_pairs {
+    () => { () };
+    ($head:ident $(, $tail:ident)*) => { ($head, nested_pairs!($($tail),*)) };
+}
+
 macro_rules! build_tuple_injector {
     ($($tuple_type:ident),*) => {
         impl<$($tuple_type: std::clone::Clone + 'static),*> TupleInjectTypes for ($($tuple_type),* ) {
-           fn read_from_injector(injector: &Injector) -> anyhow::Result<Self> {
+           type Dependencies = nested_pairs!($($tuple_type),*);
+
+           fn read_from_injector(injector: &Injector) -> Result<Self> {
                Ok(($(injector.get::<$tuple_type>()?),* ))
            }
 
@@ -0,0 +1,81 @@
+// Fields of enum variants, used by the generated code of `derive(Component)`.
+// Only fields of the selected variant are constructed, so components are built on demand
+// with `ComponentMeta::inject`, while other values, such as configs, are read from the injector.
+// The generated code calls `(&&VariantField::<T>::new()).inject(injector)`
+// and the method resolution picks `ComponentField` for components and `ValueField` otherwise.
+use crate::component_meta::ComponentMeta;
+use crate::injector::Injector;
+use std::any::{type_name, TypeId};
+use std::marker::PhantomData;
+
+pub struct VariantField<T>(PhantomData<fn() -> T>);
+
+impl<T> VariantField<T> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        VariantField(PhantomData)
+    }
+}
+
+pub trait ComponentField<T> {
+    fn inject(&self, injector: &Injector) -> anyhow::Result<T>;
+
+    fn dependencies_names(&self) -> Vec<(TypeId, &'static str)>;
+
+    fn optional_dependencies_names(&self) -> Vec<(TypeId, &'static str)>;
+
+    fn named_dependencies_names(&self) -> Vec<(&'static str, TypeId, &'static str)>;
+}
+
+// A bound component is reused, it is built before the enum as an optional dependency.
+// Otherwise it is built from its own dependencies, which are required by the enum.
+impl<T: ComponentMeta + Clone + 'static> ComponentField<T> for &VariantField<T> {
+    fn inject(&self, injector: &Injector) -> anyhow::Result<T> {
+        match injector.get_optional::<T>()? {
+            Some(value) => Ok(value),
+            None => T::inject(injector),
+        }
+    }
+
+    fn dependencies_names(&self) -> Vec<(TypeId, &'static str)> {
+        T::dependencies_names()
+    }
+
+    fn optional_dependencies_names(&self) -> Vec<(TypeId, &'static str)> {
+        let mut result = vec![(TypeId::of::<T>(), type_name::<T>())];
+        result.extend(T::optional_dependencies_names());
+        result
+    }
+
+    fn named_dependencies_names(&self) -> Vec<(&'static str, TypeId, &'static str)> {
+        T::named_dependencies_names()
+    }
+}
+
+pub trait ValueField<T> {
+    fn inject(&self, injector: &Injector) -> anyhow::Result<T>;
+
+    fn dependencies_names(&self) -> Vec<(TypeId, &'static str)>;
+
+    fn optional_dependencies_names(&self) -> Vec<(TypeId, &'static str)>;
+
+    fn named_dependencies_names(&self) -> Vec<(&'static str, TypeId, &'static str)>;
+}
+
+impl<T: Clone + 'static> ValueField<T> for VariantField<T> {
+    fn inject(&self, injector: &Injector) -> anyhow::Result<T> {
+        Ok(injector.get::<T>()?)
+    }
+
+    fn dependencies_names(&self) -> Vec<(TypeId, &'static str)> {
+        vec![(TypeId::of::<T>(), type_name::<T>())]
+    }
+
+    fn optional_dependencies_names(&self) -> Vec<(TypeId, &'static str)> {
+        vec![]
+    }
+
+    fn named_dependencies_names(&self) -> Vec<(&'static str, TypeId, &'static str)> {
+        vec![]
+    }
+}
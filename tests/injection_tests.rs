@@ -485,7 +485,7 @@ fn work_with_arc_dyn_traits() {
         x: u32,
     }
 
-    trait Test {
+    trait Test: Send + Sync {
         fn x(&self) -> u32;
     }
     impl Test for A {
@@ -510,7 +510,7 @@ fn work_with_box_dyn_traits() {
         x: u32,
     }
 
-    trait Test: DynClone {
+    trait Test: DynClone + Send + Sync {
         fn x(&self) -> u32;
     }
 
@@ -684,6 +684,7 @@ fn should_work_with_expansion_with_arc() {
     assert_eq!(y, 2);
 }
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_work_with_expansion_with_ignore() {
     #[derive(ComponentExpander)]
     struct A {
@@ -705,6 +706,7 @@ fn should_work_with_expansion_with_ignore() {
 
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_work_with_generics() {
     #[derive(ComponentExpander)]
     struct A<T> {
@@ -725,6 +727,7 @@ fn should_work_with_generics() {
 }
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn should_work_for_nested_expand() {
     #[derive(ComponentExpander, Clone)]
     struct InnerStruct {
@@ -778,4 +781,1676 @@ fn should_work_for_nested_expand_forced() {
     let x = inject.get::<InnerStruct>().unwrap();
     assert_eq!(x.x, 1);
     assert_eq!(x.y, 2);
-}
\ No newline at end of file
+}
+
+#[test]
+#[cfg(not(feature = "local"))]
+fn injector_is_send_and_sync() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    #[derive(Component, Clone)]
+    struct A {
+        x: u32,
+    }
+
+    let inject = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<A>()
+        .build()
+        .unwrap();
+    assert_send_sync(&inject);
+
+    let x = std::thread::spawn(move || inject.get::<A>().unwrap().x)
+        .join()
+        .unwrap();
+    assert_eq!(x, 1);
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    use std::task::{Context, Poll, Wake, Waker};
+
+    struct ThreadWaker(std::thread::Thread);
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => return result,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn resolve_async_builders() {
+    #[derive(Clone)]
+    struct Pool {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct Repository {
+        pool: Pool,
+    }
+
+    let future = InjectionBinder::new()
+        .instance(1u32)
+        .inject_async_fn(|(x,)| async move { Ok(Pool { x }) })
+        .inject::<Repository>()
+        .build_async();
+    let inject = block_on(future).unwrap();
+
+    let x = inject.get::<Repository>().unwrap().pool.x;
+    assert_eq!(x, 1)
+}
+
+#[test]
+fn resolve_independent_async_builders_concurrently() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::Poll;
+
+    // each builder waits until the other one has started,
+    // so sequential awaiting would never finish
+    fn wait_for_each_other(
+        started: Arc<AtomicBool>,
+        other: Arc<AtomicBool>,
+    ) -> impl std::future::Future<Output = ()> {
+        std::future::poll_fn(move |cx| {
+            started.store(true, Ordering::SeqCst);
+            if other.load(Ordering::SeqCst) {
+                Poll::Ready(())
+            } else {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        })
+    }
+
+    let a_started = Arc::new(AtomicBool::new(false));
+    let b_started = Arc::new(AtomicBool::new(false));
+    let (a1, b1) = (a_started.clone(), b_started.clone());
+    let (a2, b2) = (a_started.clone(), b_started.clone());
+
+    let future = InjectionBinder::new()
+        .inject_async_fn(move |()| {
+            let wait = wait_for_each_other(a1.clone(), b1.clone());
+            async move {
+                wait.await;
+                Ok(1u32)
+            }
+        })
+        .inject_async_fn(move |()| {
+            let wait = wait_for_each_other(b2.clone(), a2.clone());
+            async move {
+                wait.await;
+                Ok(2u64)
+            }
+        })
+        .build_async();
+    let inject = block_on(future).unwrap();
+
+    assert_eq!(inject.get::<u32>().unwrap(), 1);
+    assert_eq!(inject.get::<u64>().unwrap(), 2);
+}
+
+#[test]
+fn fail_on_sync_build_with_async_builders() {
+    #[derive(Clone)]
+    struct AsyncStruct {}
+
+    let inject_res = InjectionBinder::new()
+        .inject_async_fn(|()| async { Ok(AsyncStruct {}) })
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("use build_async"));
+    assert!(err_string.contains("AsyncStruct"));
+}
+
+#[test]
+#[cfg(not(feature = "local"))]
+fn resolve_values_in_parallel() {
+    #[derive(Component, Clone)]
+    struct A {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        a: A,
+        y: u64,
+    }
+    #[derive(Component, Clone)]
+    struct C {
+        a: A,
+        b: B,
+    }
+
+    let inject = InjectionBinder::new()
+        .inject::<C>()
+        .inject::<B>()
+        .inject::<A>()
+        .instance(1u32)
+        .instance(2u64)
+        .build_parallel()
+        .unwrap();
+
+    let x = inject.get::<C>().unwrap();
+    assert_eq!(x.a.x, 1);
+    assert_eq!(x.b.a.x, 1);
+    assert_eq!(x.b.y, 2);
+}
+
+#[test]
+#[cfg(not(feature = "local"))]
+fn fail_on_all_parallel_builders_of_layer() {
+    #[derive(Clone)]
+    struct FirstFailed {}
+    #[derive(Clone)]
+    struct SecondFailed {}
+
+    let inject_res = InjectionBinder::new()
+        .inject_fn_ok(|()| -> anyhow::Result<FirstFailed> { Err(anyhow::anyhow!("first error")) })
+        .inject_fn_ok(|()| -> anyhow::Result<SecondFailed> { Err(anyhow::anyhow!("second error")) })
+        .instance(1u32)
+        .build_parallel();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("FirstFailed"));
+    assert!(err_string.contains("first error"));
+    assert!(err_string.contains("SecondFailed"));
+    assert!(err_string.contains("second error"));
+}
+
+#[test]
+fn start_and_stop_components_in_dependencies_order() {
+    type Log = Arc<std::sync::Mutex<Vec<&'static str>>>;
+
+    #[derive(Component, Clone)]
+    struct Pool {
+        log: Log,
+    }
+    impl mydi::Lifecycle for Pool {
+        fn start(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("start pool");
+            Ok(())
+        }
+        fn stop(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("stop pool");
+            Ok(())
+        }
+    }
+
+    #[derive(Component, Clone)]
+    #[component(on_start = Server::run, on_stop = Server::shutdown)]
+    struct Server {
+        _pool: Pool,
+        log: Log,
+    }
+    impl Server {
+        fn run(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("start server");
+            Ok(())
+        }
+        fn shutdown(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("stop server");
+            Ok(())
+        }
+    }
+
+    let log: Log = Default::default();
+    let inject = InjectionBinder::new()
+        .inject::<Server>().lifecycle()
+        .inject::<Pool>().lifecycle()
+        .instance(log.clone())
+        .build()
+        .unwrap();
+
+    inject.start_all().unwrap();
+    inject.stop_all().unwrap();
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec!["start pool", "start server", "stop server", "stop pool"]
+    );
+}
+
+#[test]
+fn stop_started_components_on_start_failure() {
+    type Log = Arc<std::sync::Mutex<Vec<&'static str>>>;
+
+    #[derive(Component, Clone)]
+    #[component(on_start = Pool::start, on_stop = Pool::stop)]
+    struct Pool {
+        log: Log,
+    }
+    impl Pool {
+        fn start(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("start pool");
+            Ok(())
+        }
+        fn stop(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("stop pool");
+            Ok(())
+        }
+    }
+
+    #[derive(Component, Clone)]
+    #[component(on_start = FailedServer::run)]
+    struct FailedServer {
+        _pool: Pool,
+    }
+    impl FailedServer {
+        fn run(&self) -> anyhow::Result<()> {
+            Err(anyhow::anyhow!("port is busy"))
+        }
+    }
+
+    let log: Log = Default::default();
+    let inject = InjectionBinder::new()
+        .inject::<FailedServer>().lifecycle()
+        .inject::<Pool>().lifecycle()
+        .instance(log.clone())
+        .build()
+        .unwrap();
+
+    let err_string = format!("{:#}", anyhow::Error::from(inject.start_all().err().unwrap()));
+    assert!(err_string.contains("FailedServer"));
+    assert!(err_string.contains("port is busy"));
+    assert_eq!(*log.lock().unwrap(), vec!["start pool", "stop pool"]);
+}
+
+#[test]
+fn register_derived_lifecycle_hooks_on_inject() {
+    type Log = Arc<std::sync::Mutex<Vec<&'static str>>>;
+
+    #[derive(Component, Clone)]
+    #[component(on_start = Consumer::start, on_stop = Consumer::stop)]
+    struct Consumer {
+        log: Log,
+    }
+    impl Consumer {
+        fn start(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("start consumer");
+            Ok(())
+        }
+        fn stop(&self) -> anyhow::Result<()> {
+            self.log.lock().unwrap().push("stop consumer");
+            Ok(())
+        }
+    }
+
+    let log: Log = Default::default();
+    let inject = InjectionBinder::new()
+        .inject::<Consumer>()
+        .instance(log.clone())
+        .build()
+        .unwrap();
+    inject.start_all().unwrap();
+    inject.stop_all().unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["start consumer", "stop consumer"]);
+
+    // calling lifecycle again doesn't register the hooks twice
+    let log: Log = Default::default();
+    let inject = InjectionBinder::new()
+        .inject::<Consumer>().lifecycle()
+        .instance(log.clone())
+        .build()
+        .unwrap();
+    inject.start_all().unwrap();
+    assert_eq!(*log.lock().unwrap(), vec!["start consumer"]);
+}
+
+#[test]
+fn fail_on_lifecycle_of_transient_types() {
+    #[derive(Component, Clone)]
+    #[component(on_start = Connection::open)]
+    struct Connection {}
+    impl Connection {
+        fn open(&self) -> anyhow::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = InjectionBinder::new()
+        .inject_transient::<Connection>().lifecycle()
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, mydi::Error::TransientLifecycle { .. }));
+    assert!(err.to_string().contains("Connection"));
+}
+
+#[test]
+fn resolve_transient_values_on_every_get() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Clone)]
+    struct RequestId(u32);
+    #[derive(Component, Clone)]
+    struct Handler {
+        request_id: RequestId,
+    }
+
+    let counter = Arc::new(AtomicU32::new(0));
+    let inject = InjectionBinder::new()
+        .inject_fn_transient(move |(prefix,): (u32,)| {
+            RequestId(prefix + counter.fetch_add(1, Ordering::SeqCst))
+        })
+        .inject_transient::<Handler>()
+        .instance(100u32)
+        .build()
+        .unwrap();
+
+    assert_eq!(inject.get::<RequestId>().unwrap().0, 100);
+    assert_eq!(inject.get::<RequestId>().unwrap().0, 101);
+    assert_eq!(inject.get::<Handler>().unwrap().request_id.0, 102);
+}
+
+#[test]
+fn fail_on_missing_transient_dependencies() {
+    #[derive(Clone)]
+    struct MissingDep {}
+    #[derive(Component, Clone)]
+    struct TransientStruct {
+        _missing: MissingDep,
+    }
+
+    let inject_res = InjectionBinder::new()
+        .inject_transient::<TransientStruct>()
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("Missing injection values"));
+    assert!(err_string.contains("TransientStruct"));
+    assert!(err_string.contains("MissingDep"));
+}
+
+#[test]
+fn resolve_values_in_child_scope() {
+    #[derive(Component, Clone)]
+    struct Repository {
+        x: u32,
+    }
+    #[derive(Clone)]
+    struct RequestContext {
+        user: &'static str,
+    }
+    #[derive(Component, Clone)]
+    struct Handler {
+        repository: Repository,
+        context: RequestContext,
+    }
+
+    let parent = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<Repository>()
+        .build()
+        .unwrap();
+
+    let child = parent
+        .scope(
+            InjectionBinder::new()
+                .instance(RequestContext { user: "admin" })
+                .inject::<Handler>(),
+        )
+        .unwrap();
+
+    let handler = child.get::<Handler>().unwrap();
+    assert_eq!(handler.repository.x, 1);
+    assert_eq!(handler.context.user, "admin");
+    assert_eq!(child.get::<u32>().unwrap(), 1);
+
+    assert!(parent.get::<RequestContext>().is_err());
+    assert!(parent.get::<Handler>().is_err());
+}
+
+#[test]
+fn shadow_parent_values_in_child_scope() {
+    #[derive(Component, Clone)]
+    struct Repository {
+        x: u32,
+        #[component(named = "db.timeout")]
+        timeout: u64,
+    }
+
+    let parent = InjectionBinder::new()
+        .instance(1u32)
+        .instance_named("db.timeout", 5u64)
+        .inject::<Repository>()
+        .build()
+        .unwrap();
+
+    let child = parent
+        .scope(
+            InjectionBinder::new()
+                .instance(2u32)
+                .instance_named("db.timeout", 10u64)
+                .inject::<Repository>(),
+        )
+        .unwrap();
+
+    let repository = child.get::<Repository>().unwrap();
+    assert_eq!(repository.x, 2);
+    assert_eq!(repository.timeout, 10);
+    assert_eq!(child.get::<u32>().unwrap(), 2);
+
+    let repository = parent.get::<Repository>().unwrap();
+    assert_eq!(repository.x, 1);
+    assert_eq!(repository.timeout, 5);
+}
+
+#[test]
+fn fail_on_missing_values_in_child_scope() {
+    #[derive(Clone)]
+    struct MissingContext {}
+    #[derive(Component, Clone)]
+    struct ScopedHandler {
+        _x: u32,
+        _context: MissingContext,
+    }
+
+    let parent = InjectionBinder::new()
+        .instance(1u32)
+        .build()
+        .unwrap();
+
+    let child_res = parent.scope(InjectionBinder::new().inject::<ScopedHandler>());
+
+    assert!(child_res.is_err());
+    let err_string = child_res.err().unwrap().to_string();
+    assert!(err_string.contains("Missing injection values"));
+    assert!(err_string.contains("ScopedHandler"));
+    assert!(err_string.contains("MissingContext"));
+    assert!(!err_string.contains("u32"));
+}
+
+#[test]
+fn resolve_multibindings_from_merged_binders() {
+    trait HealthCheck: Send + Sync {
+        fn name(&self) -> String;
+    }
+    #[derive(Component, Clone)]
+    struct DbCheck {}
+    impl HealthCheck for DbCheck {
+        fn name(&self) -> String {
+            "db".to_string()
+        }
+    }
+    #[derive(Component, Clone)]
+    struct KafkaCheck {}
+    impl HealthCheck for KafkaCheck {
+        fn name(&self) -> String {
+            "kafka".to_string()
+        }
+    }
+    #[derive(Component, Clone)]
+    struct HealthController {
+        checks: Vec<Arc<dyn HealthCheck>>,
+    }
+
+    let binder1 = InjectionBinder::new()
+        .inject::<DbCheck>().add_to_set(erase!(Arc<dyn HealthCheck>))
+        .inject::<HealthController>();
+    let binder2 = InjectionBinder::new()
+        .inject::<KafkaCheck>().add_to_set(erase!(Arc<dyn HealthCheck>));
+
+    let inject = binder1.merge(binder2).build().unwrap();
+
+    let mut names: Vec<_> = inject
+        .get::<HealthController>()
+        .unwrap()
+        .checks
+        .iter()
+        .map(|x| x.name())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["db", "kafka"]);
+}
+
+#[test]
+fn resolve_map_multibindings() {
+    use std::collections::HashMap;
+
+    #[derive(Component, Clone)]
+    struct UsersRoute {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct OrdersRoute {
+        x: u32,
+    }
+
+    let inject = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<UsersRoute>().add_to_map("/users", |x| x.x)
+        .inject::<OrdersRoute>().add_to_map("/orders", |x| x.x + 1)
+        .build()
+        .unwrap();
+
+    let routes = inject.get::<HashMap<&'static str, u32>>().unwrap();
+    assert_eq!(routes.len(), 2);
+    assert_eq!(routes["/users"], 1);
+    assert_eq!(routes["/orders"], 2);
+}
+
+#[test]
+fn fail_on_duplicate_map_multibinding_keys() {
+    #[derive(Component, Clone)]
+    struct FirstRoute {}
+    #[derive(Component, Clone)]
+    struct SecondRoute {}
+
+    let inject_res = InjectionBinder::new()
+        .inject::<FirstRoute>().add_to_map("/users", |_| 1u32)
+        .inject::<SecondRoute>().add_to_map("/users", |_| 2u32)
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = format!("{:#}", anyhow::Error::from(inject_res.err().unwrap()));
+    assert!(err_string.contains("Duplicate keys found"));
+}
+
+#[test]
+fn resolve_optional_dependencies() {
+    #[derive(Component, Clone)]
+    struct SentryClient {
+        x: u32,
+    }
+    #[derive(Clone)]
+    struct MissingClient {}
+    #[derive(Component, Clone)]
+    struct Service {
+        #[component(optional)]
+        sentry: Option<SentryClient>,
+        #[component(optional)]
+        missing: Option<MissingClient>,
+    }
+
+    let inject = InjectionBinder::new()
+        .inject::<Service>()
+        .inject::<SentryClient>()
+        .instance(1u32)
+        .build()
+        .unwrap();
+
+    let service = inject.get::<Service>().unwrap();
+    assert_eq!(service.sentry.unwrap().x, 1);
+    assert!(service.missing.is_none());
+}
+
+#[test]
+fn override_bindings() {
+    #[derive(Component, Clone)]
+    struct Client {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct Repository {
+        y: u64,
+    }
+    #[derive(Component, Clone)]
+    struct Service {
+        client: Client,
+        repository: Repository,
+    }
+
+    let production = InjectionBinder::new()
+        .instance(1u32)
+        .instance(2u64)
+        .inject::<Client>()
+        .inject::<Repository>()
+        .inject::<Service>();
+
+    let inject = production
+        .override_instance(Client { x: 10 })
+        .override_fn(|()| Repository { y: 20 })
+        .override_binder(InjectionBinder::new().instance(3u32))
+        .build()
+        .unwrap();
+
+    let service = inject.get::<Service>().unwrap();
+    assert_eq!(service.client.x, 10);
+    assert_eq!(service.repository.y, 20);
+    assert_eq!(inject.get::<u32>().unwrap(), 3);
+}
+
+#[test]
+fn fail_on_override_of_unbound_type() {
+    #[derive(Clone)]
+    struct NeverBound {}
+
+    let inject_res = InjectionBinder::new()
+        .instance(1u32)
+        .override_instance(NeverBound {})
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("Overridden types were never bound"));
+    assert!(err_string.contains("NeverBound"));
+}
+
+#[test]
+fn override_named_bindings() {
+    let inject = InjectionBinder::new()
+        .instance_named("http.timeout", 5u32)
+        .instance_named("http.retry_delay", 1u32)
+        .instance_named("db.timeout", 10u32)
+        .override_named("db.timeout", 20u32)
+        .override_binder(InjectionBinder::new().instance_named("http.timeout", 7u32))
+        .build()
+        .unwrap();
+
+    assert_eq!(inject.get_named::<u32>("http.timeout").unwrap(), 7);
+    assert_eq!(inject.get_named::<u32>("http.retry_delay").unwrap(), 1);
+    assert_eq!(inject.get_named::<u32>("db.timeout").unwrap(), 20);
+}
+
+#[test]
+fn fail_on_override_of_unbound_name() {
+    let inject_res = InjectionBinder::new()
+        .instance_named("http.timeout", 5u32)
+        .override_binder(InjectionBinder::new().instance_named("http.timeot", 7u32))
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("Overridden types were never bound"));
+    assert!(err_string.contains("http.timeot"));
+}
+
+#[test]
+fn fail_on_duplicate_instances() {
+    let inject_res = InjectionBinder::new()
+        .instance(1u32)
+        .instance(2u32)
+        .build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("Dependencies duplications found"));
+    assert!(err_string.contains("u32"));
+    assert_eq!(err_string.matches("tests/injection_tests.rs").count(), 2);
+}
+
+#[test]
+fn fail_on_duplicate_instances_in_merged_binders() {
+    #[derive(Component, Clone)]
+    struct DuplicateInstance {
+        _x: u32,
+    }
+
+    let binder1 = InjectionBinder::new()
+        .instance(1u32)
+        .instance(DuplicateInstance { _x: 1 });
+    let binder2 = InjectionBinder::new()
+        .inject::<DuplicateInstance>();
+
+    let inject_res = binder1.merge(binder2).build();
+
+    assert!(inject_res.is_err());
+    let err_string = inject_res.err().unwrap().to_string();
+    assert!(err_string.contains("Dependencies duplications found"));
+    assert!(err_string.contains("DuplicateInstance"));
+    assert_eq!(err_string.matches("tests/injection_tests.rs").count(), 2);
+}
+
+#[test]
+fn errors_are_structured() {
+    #[derive(Clone)]
+    struct MissingDep {}
+    #[derive(Component, Clone)]
+    struct StructWithoutDep {
+        _missing: MissingDep,
+    }
+
+    let inject_res = InjectionBinder::new()
+        .inject::<StructWithoutDep>()
+        .build();
+
+    match inject_res.err().unwrap() {
+        mydi::Error::MissingDependencies { dependencies, .. } => {
+            assert_eq!(dependencies.len(), 1);
+            let dependency = &dependencies[0];
+            assert_eq!(dependency.dependent.type_id, std::any::TypeId::of::<StructWithoutDep>());
+            assert!(dependency.dependent.debug_line.is_some());
+            assert_eq!(dependency.missing.len(), 1);
+            assert_eq!(dependency.missing[0].type_id, std::any::TypeId::of::<MissingDep>());
+        }
+        err => panic!("unexpected error {}", err),
+    }
+
+    let inject = InjectionBinder::new().instance(1u32).build().unwrap();
+    let err = inject.get::<u64>().err().unwrap();
+    assert!(matches!(err, mydi::Error::MissingValue { .. }));
+    assert!(err.to_string().contains("Missing value of type u64"));
+}
+
+#[test]
+fn builder_errors_are_kept_as_source() {
+    use std::error::Error;
+
+    #[derive(Clone)]
+    struct FailedStruct {}
+
+    let inject_res = InjectionBinder::new()
+        .inject_fn_ok(|(_x,): (u32,)| -> anyhow::Result<FailedStruct> {
+            Err(anyhow::anyhow!("connection refused"))
+        })
+        .instance(1u32)
+        .build();
+
+    let err = inject_res.err().unwrap();
+    match &err {
+        mydi::Error::BuilderFailed { type_info, .. } => {
+            assert_eq!(type_info.type_id, std::any::TypeId::of::<FailedStruct>());
+        }
+        err => panic!("unexpected error {}", err),
+    }
+    assert_eq!(err.source().unwrap().to_string(), "connection refused");
+    assert!(err.to_string().contains("FailedStruct"));
+    // the cause is printed once by chain printers
+    assert!(!err.to_string().contains("connection refused"));
+    let chain = format!("{:#}", anyhow::Error::from(err));
+    assert_eq!(chain.matches("connection refused").count(), 1);
+}
+
+#[test]
+fn cycle_is_reported_as_path() {
+    #[derive(Component, Clone)]
+    struct A {
+        _b: B,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        _c: C,
+    }
+    #[derive(Component, Clone)]
+    struct C {
+        _a: Box<A>,
+        _x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct DependsOnCycle {
+        _a: Box<A>,
+    }
+
+    let binder = InjectionBinder::new()
+        .inject::<DependsOnCycle>()
+        .inject::<Box<A>>()
+        .inject::<B>()
+        .inject::<C>()
+        .instance(1u32)
+        .void();
+
+    let err = binder.verify(Default::default(), true).err().unwrap();
+    let err_string = err.to_string();
+    assert!(err_string.contains("A> -> B -> C -> Box<"));
+    assert!(err_string.contains("into C"));
+    assert!(!err_string.contains("DependsOnCycle"));
+    match err {
+        mydi::Error::Cycle { cycles, .. } => {
+            assert_eq!(cycles.len(), 1);
+            assert_eq!(cycles[0].path.len(), 3);
+            assert!(cycles[0].path.iter().all(|x| x.debug_line.is_some()));
+        }
+        err => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn export_graph() {
+    #[derive(Component, Clone)]
+    struct A {
+        _x: u32,
+        _b: mydi::Lazy<B>,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        _a: A,
+    }
+
+    let binder = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<A>()
+        .auto_box()
+        .inject::<B>()
+        .inject::<mydi::Lazy<B>>()
+        .void();
+
+    let dot = binder.to_dot(true);
+    assert!(dot.starts_with("digraph dependencies {"));
+    assert!(dot.contains("n0 [label=\"u32\", shape=ellipse"));
+    assert!(dot.contains("[label=\"A\", style=filled, fillcolor=white"));
+    assert!(dot.contains("[label=\"Box<injection_tests::export_graph::A>\", style=filled, fillcolor=lightblue"));
+    assert!(dot.contains("n1 -> n0;"));
+    assert!(dot.contains("[style=dashed]"));
+
+    let mermaid = binder.to_mermaid(true);
+    assert!(mermaid.starts_with("graph TD"));
+    assert!(mermaid.contains("n0([\"u32\"])"));
+    assert!(mermaid.contains("n1 --> n0"));
+    assert!(mermaid.contains("-.->"));
+    assert!(mermaid.contains("class n0 instance"));
+}
+
+#[test]
+fn list_bindings() {
+    #[derive(Component, Clone)]
+    struct A {
+        _x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        _a: A,
+    }
+
+    let binder = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<A>()
+        .inject::<B>()
+        .inject_fn(|(b,): (B,)| -> Arc<B> { Arc::new(b) })
+        .inject_transient::<mydi::Lazy<B>>()
+        .void();
+
+    let bindings = binder.bindings();
+    let names: Vec<_> = bindings.iter().map(|x| x.short_name).collect();
+    assert_eq!(names[..4], ["u32", "A", "B", "Arc<injection_tests::list_bindings::B>"]);
+    assert_eq!(bindings[4].type_id, std::any::TypeId::of::<mydi::Lazy<B>>());
+    assert!(bindings[4].transient);
+    let kinds: Vec<_> = bindings.iter().map(|x| x.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            mydi::BindingKind::Instance,
+            mydi::BindingKind::Component,
+            mydi::BindingKind::Component,
+            mydi::BindingKind::Function,
+            mydi::BindingKind::Lazy,
+        ]
+    );
+    assert_eq!(bindings[2].dependencies[0].0, std::any::TypeId::of::<A>());
+    assert!(bindings[2].debug_line.is_some());
+    assert!(bindings[0].built);
+    assert!(!bindings[1].built);
+
+    let inject = binder.build().unwrap();
+    let built: Vec<_> = inject.bindings().iter().map(|x| x.built).collect();
+    assert_eq!(built, vec![true, true, true, true, false]);
+}
+
+#[test]
+fn module_macro() {
+    #[derive(Component, Clone)]
+    struct A {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        a: Arc<A>,
+        _lazy_c: mydi::Lazy<C>,
+        #[component(default)]
+        _y: u64,
+    }
+    #[derive(Component, Clone)]
+    struct C {
+        _b: B,
+        s: String,
+    }
+
+    let binder = mydi::module! {
+        external String;
+        instance u32 = 1;
+        inject Arc<A>;
+        inject B;
+        inject C;
+        inject mydi::Lazy<C>;
+    };
+    let inject = binder.instance("s".to_string()).build().unwrap();
+
+    let b: B = inject.get().unwrap();
+    assert_eq!(b.a.x, 1);
+    let c: C = inject.get().unwrap();
+    assert_eq!(c.s, "s");
+}
+
+#[test]
+fn build_only_root_dependencies() {
+    #[derive(Component, Clone)]
+    struct A {
+        x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct App {
+        a: A,
+    }
+    #[derive(Clone)]
+    struct MissingDep {}
+    #[derive(Component, Clone)]
+    struct Unused {
+        _missing: MissingDep,
+    }
+
+    let binder = InjectionBinder::new()
+        .instance(1u32)
+        .instance(2u64)
+        .inject::<A>()
+        .inject::<Unused>()
+        .inject::<App>()
+        .inject_fn(|(_x,): (u32,)| -> String { panic!("must not be built") })
+        .void();
+
+    let err = binder.verify_unused::<App>(true).err().unwrap();
+    match &err {
+        mydi::Error::Unused { types, .. } => {
+            let names: Vec<_> = types.iter().map(|x| x.type_name).collect();
+            assert_eq!(names, vec!["u64", std::any::type_name::<Unused>(), "alloc::string::String"]);
+        }
+        err => panic!("unexpected error {}", err),
+    }
+    assert!(err.to_string().contains("Unused bindings found: u64, Unused, String"));
+
+    let app: App = binder.build_root::<App>().unwrap();
+    assert_eq!(app.a.x, 1);
+
+    // unreachable named duplicates are pruned as well
+    let app: App = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<A>()
+        .inject::<App>()
+        .instance_named("unused", 1u8)
+        .merge(InjectionBinder::new().instance_named("unused", 2u8))
+        .build_root::<App>()
+        .unwrap();
+    assert_eq!(app.a.x, 1);
+
+    // overrides of types that were never bound are still reported
+    let err = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<A>()
+        .inject::<App>()
+        .override_instance(3i8)
+        .build_root::<App>()
+        .err()
+        .unwrap();
+    assert!(matches!(err, mydi::Error::MissingOverrides { .. }));
+}
+
+#[test]
+fn build_with_report() {
+    #[derive(Component, Clone)]
+    struct A {
+        _x: u32,
+    }
+    #[derive(Component, Clone)]
+    struct B {
+        _a: A,
+    }
+
+    let (inject, report) = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<B>()
+        .inject::<A>()
+        .build_with_report()
+        .unwrap();
+    assert!(inject.get::<B>().is_ok());
+
+    let entries: Vec<_> = report
+        .entries
+        .iter()
+        .map(|x| (x.type_id, x.order, x.layer))
+        .collect();
+    assert_eq!(
+        entries,
+        vec![
+            (std::any::TypeId::of::<A>(), 0, 0),
+            (std::any::TypeId::of::<B>(), 1, 1)
+        ]
+    );
+    assert_eq!(report.entries[1].dependencies[0].0, std::any::TypeId::of::<A>());
+
+    let table = report.to_string();
+    assert!(table.starts_with("order  layer"));
+    assert!(table.contains("  B  "));
+    assert!(table.contains("total: "));
+
+    let json = report.to_json();
+    assert!(json.starts_with("[{\"order\":0,\"layer\":0,\"type\":\"injection_tests::build_with_report::A\""));
+    assert!(json.contains("\"dependencies\":[\"injection_tests::build_with_report::A\"]"));
+
+    let trace = report.to_chrome_trace();
+    assert!(trace.starts_with("{\"traceEvents\":[{\"name\":\"A\",\"cat\":\"mydi\",\"ph\":\"X\""));
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn trace_builders() {
+    use std::sync::Mutex;
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    #[derive(Default)]
+    struct Recorder {
+        records: Arc<Mutex<Vec<String>>>,
+    }
+
+    struct FieldsVisitor<'a>(&'a mut String);
+
+    impl Visit for FieldsVisitor<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut record = span.metadata().name().to_string();
+            span.record(&mut FieldsVisitor(&mut record));
+            let mut records = self.records.lock().unwrap();
+            records.push(record);
+            Id::from_u64(records.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            let mut record = String::from("event");
+            event.record(&mut FieldsVisitor(&mut record));
+            self.records.lock().unwrap().push(record);
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[derive(Component, Clone)]
+    struct A {
+        _x: u32,
+    }
+
+    let recorder = Recorder::default();
+    let records = recorder.records.clone();
+    tracing::subscriber::with_default(recorder, || {
+        let inject = InjectionBinder::new()
+            .instance(1u32)
+            .inject::<A>()
+            .build()
+            .unwrap();
+        assert!(inject.get::<u64>().is_err());
+        assert!(InjectionBinder::new().inject::<A>().build().is_err());
+    });
+
+    let records = records.lock().unwrap();
+    assert!(records[0].starts_with("mydi_build type_name=\"injection_tests::trace_builders::A\""));
+    assert!(records[0].contains("debug_line=\"tests/injection_tests.rs"));
+    assert!(records[0].contains("dependencies=\"u32\""));
+    assert!(records[1].contains("missing value"));
+    assert!(records[1].contains("type_name=\"u64\""));
+    assert!(records[2].contains("dependencies verification failed"));
+}
+
+#[test]
+fn resolve_tuple_structs() {
+    #[derive(Component, Clone)]
+    struct Pool(u32);
+    #[derive(Component, Clone)]
+    struct UsersRepository(Pool, #[component(default)] u64, Option<String>);
+
+    let inject = InjectionBinder::new()
+        .instance(1u32)
+        .instance(Some("users".to_string()))
+        .inject::<Pool>()
+        .inject::<UsersRepository>()
+        .build()
+        .unwrap();
+
+    let repository: UsersRepository = inject.get().unwrap();
+    assert_eq!(repository.0 .0, 1);
+    assert_eq!(repository.1, 0);
+    assert_eq!(repository.2, Some("users".to_string()));
+}
+
+#[test]
+fn resolve_enums_by_selector() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static S3_BUILDS: AtomicUsize = AtomicUsize::new(0);
+    static LOCAL_BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct StorageConfig {
+        kind: &'static str,
+    }
+    #[derive(Component, Clone)]
+    struct S3Storage {
+        bucket: String,
+        #[component(default_with = || S3_BUILDS.fetch_add(1, Ordering::SeqCst))]
+        _build: usize,
+    }
+    #[derive(Component, Clone)]
+    struct LocalStorage {
+        _path: u32,
+        #[component(default_with = || LOCAL_BUILDS.fetch_add(1, Ordering::SeqCst))]
+        _build: usize,
+    }
+    #[derive(Component, Clone)]
+    #[component(select = select_storage)]
+    enum Storage {
+        S3(S3Storage),
+        Local { _storage: LocalStorage },
+        Disabled,
+    }
+
+    fn select_storage(config: StorageConfig) -> StorageVariant {
+        match config.kind {
+            "s3" => StorageVariant::S3,
+            "local" => StorageVariant::Local,
+            _ => StorageVariant::Disabled,
+        }
+    }
+
+    // variant components are not bound, only the selected one is built
+    let inject = InjectionBinder::new()
+        .instance(StorageConfig { kind: "s3" })
+        .instance("bucket".to_string())
+        .instance(1u32)
+        .inject::<Storage>()
+        .build()
+        .unwrap();
+    match inject.get::<Storage>().unwrap() {
+        Storage::S3(storage) => assert_eq!(storage.bucket, "bucket"),
+        _ => panic!("unexpected variant"),
+    }
+    assert_eq!(S3_BUILDS.load(Ordering::SeqCst), 1);
+    assert_eq!(LOCAL_BUILDS.load(Ordering::SeqCst), 0);
+
+    let inject = InjectionBinder::new()
+        .instance(StorageConfig { kind: "none" })
+        .instance("bucket".to_string())
+        .instance(1u32)
+        .inject::<Storage>()
+        .build()
+        .unwrap();
+    assert!(matches!(inject.get::<Storage>().unwrap(), Storage::Disabled));
+    assert_eq!(S3_BUILDS.load(Ordering::SeqCst), 1);
+    assert_eq!(LOCAL_BUILDS.load(Ordering::SeqCst), 0);
+
+    let err = InjectionBinder::new()
+        .inject::<Storage>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("StorageConfig"));
+
+    // dependencies of all variants are verified, whatever variant is selected
+    let err = InjectionBinder::new()
+        .instance(StorageConfig { kind: "s3" })
+        .instance("bucket".to_string())
+        .inject::<Storage>()
+        .verify(std::collections::HashSet::new(), true)
+        .err()
+        .unwrap();
+    match &err {
+        mydi::Error::MissingDependencies { dependencies, .. } => {
+            let missing: Vec<_> = dependencies.iter().flat_map(|x| x.missing.iter()).collect();
+            assert_eq!(missing.len(), 1);
+            assert_eq!(missing[0].type_name, "u32");
+        }
+        err => panic!("unexpected error {}", err),
+    }
+}
+
+#[test]
+fn enum_variants_reuse_bound_components() {
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static BUILDS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone)]
+    struct Bucket(&'static str);
+    #[derive(Component, Clone)]
+    struct S3Storage {
+        bucket: Bucket,
+        #[component(default_with = || BUILDS.fetch_add(1, Ordering::SeqCst))]
+        _build: usize,
+    }
+    #[derive(Component, Clone)]
+    #[component(select = select_storage)]
+    enum Storage {
+        S3(S3Storage),
+        Local(PathBuf),
+    }
+
+    fn select_storage(s3: bool) -> StorageVariant {
+        if s3 {
+            StorageVariant::S3
+        } else {
+            StorageVariant::Local
+        }
+    }
+
+    // the bound singleton is built once and reused by the enum
+    let inject = InjectionBinder::new()
+        .instance(true)
+        .instance(Bucket("bucket"))
+        .instance(PathBuf::from("/tmp"))
+        .inject::<S3Storage>()
+        .inject::<Storage>()
+        .build()
+        .unwrap();
+    match inject.get::<Storage>().unwrap() {
+        Storage::S3(storage) => assert_eq!(storage.bucket.0, "bucket"),
+        _ => panic!("unexpected variant"),
+    }
+    assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+
+    // plain values are read from the injector
+    let inject = InjectionBinder::new()
+        .instance(false)
+        .instance(Bucket("bucket"))
+        .instance(PathBuf::from("/tmp"))
+        .inject::<Storage>()
+        .build()
+        .unwrap();
+    match inject.get::<Storage>().unwrap() {
+        Storage::Local(path) => assert_eq!(path, PathBuf::from("/tmp")),
+        _ => panic!("unexpected variant"),
+    }
+    assert_eq!(BUILDS.load(Ordering::SeqCst), 1);
+
+    // the dependency of the selected variant is missing
+    let err = InjectionBinder::new()
+        .instance(true)
+        .instance(PathBuf::from("/tmp"))
+        .inject::<Storage>()
+        .verify(std::collections::HashSet::new(), true)
+        .err()
+        .unwrap();
+    assert!(matches!(err, mydi::Error::MissingDependencies { .. }));
+    assert!(err.to_string().contains("Bucket"));
+}
+
+#[test]
+fn resolve_tagged_fields() {
+    use mydi::tags::Tagged;
+
+    #[derive(Clone)]
+    struct KafkaClient {
+        cluster: &'static str,
+    }
+    struct Tag1;
+    struct Tag2;
+
+    #[derive(Component, Clone)]
+    struct Service {
+        #[component(tag = Tag1)]
+        first: KafkaClient,
+        #[component(tag = Tag2)]
+        second: KafkaClient,
+    }
+    #[derive(Component, Clone)]
+    struct TupleService(#[component(tag = Tag2)] KafkaClient);
+
+    let inject = InjectionBinder::new()
+        .instance(Tagged::<_, Tag1>::new(KafkaClient { cluster: "first" }))
+        .instance(Tagged::<_, Tag2>::new(KafkaClient { cluster: "second" }))
+        .inject::<Service>()
+        .inject::<TupleService>()
+        .build()
+        .unwrap();
+
+    let service: Service = inject.get().unwrap();
+    assert_eq!(service.first.cluster, "first");
+    assert_eq!(service.second.cluster, "second");
+    let service: TupleService = inject.get().unwrap();
+    assert_eq!(service.0.cluster, "second");
+
+    let err = InjectionBinder::new()
+        .instance(Tagged::<_, Tag1>::new(KafkaClient { cluster: "first" }))
+        .inject::<Service>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Tagged<"));
+    assert!(err.to_string().contains("Tag2>"));
+}
+
+#[test]
+fn resolve_named_values() {
+    use std::time::Duration;
+
+    #[derive(Component, Clone)]
+    struct HttpClient {
+        #[component(named = "http.timeout")]
+        timeout: Duration,
+        #[component(named = "http.retry_delay")]
+        retry_delay: Duration,
+    }
+
+    let inject = InjectionBinder::new()
+        .instance_named("http.timeout", Duration::from_secs(5))
+        .instance_named("http.retry_delay", Duration::from_secs(1))
+        .inject::<HttpClient>()
+        .build()
+        .unwrap();
+
+    let client: HttpClient = inject.get().unwrap();
+    assert_eq!(client.timeout, Duration::from_secs(5));
+    assert_eq!(client.retry_delay, Duration::from_secs(1));
+    assert_eq!(
+        inject.get_named::<Duration>("http.timeout").unwrap(),
+        Duration::from_secs(5)
+    );
+    let err = inject.get_named::<Duration>("http.unknown").err().unwrap();
+    assert!(matches!(err, mydi::Error::MissingNamedValue { .. }));
+    assert!(err.to_string().contains("\"http.unknown\""));
+
+    let child = inject
+        .scope(InjectionBinder::new().instance_named("request.timeout", Duration::from_secs(2)))
+        .unwrap();
+    assert_eq!(
+        child.get_named::<Duration>("http.retry_delay").unwrap(),
+        Duration::from_secs(1)
+    );
+}
+
+#[test]
+fn fail_on_missing_and_duplicate_named_values() {
+    use std::time::Duration;
+
+    #[derive(Component, Clone)]
+    struct HttpClient {
+        #[component(named = "http.timeout")]
+        _timeout: Duration,
+    }
+
+    let err = InjectionBinder::new()
+        .instance_named("http.retry_delay", Duration::from_secs(1))
+        .inject::<HttpClient>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(matches!(err, mydi::Error::MissingNamedDependencies { .. }));
+    let err_string = err.to_string();
+    assert!(err_string.contains("Missing named injection values"));
+    assert!(err_string.contains("HttpClient"));
+    assert!(err_string.contains("\"http.timeout\": core::time::Duration"));
+
+    let err = InjectionBinder::new()
+        .instance_named("http.timeout", Duration::from_secs(1))
+        .merge(InjectionBinder::new().instance_named("http.timeout", Duration::from_secs(2)))
+        .inject::<HttpClient>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Named values duplications found: \"http.timeout\""));
+}
+
+#[test]
+fn verify_named_values_of_enum_variants() {
+    use std::time::Duration;
+
+    #[derive(Component, Clone)]
+    #[component(select = select_cache)]
+    enum Cache {
+        Mem {
+            #[component(named = "mem.ttl")]
+            ttl: Duration,
+        },
+        Disabled,
+    }
+
+    fn select_cache(enabled: bool) -> CacheVariant {
+        if enabled {
+            CacheVariant::Mem
+        } else {
+            CacheVariant::Disabled
+        }
+    }
+
+    let err = InjectionBinder::new()
+        .instance(true)
+        .inject::<Cache>()
+        .verify(std::collections::HashSet::new(), true)
+        .err()
+        .unwrap();
+    assert!(matches!(err, mydi::Error::MissingNamedDependencies { .. }));
+    assert!(err.to_string().contains("\"mem.ttl\""));
+
+    let inject = InjectionBinder::new()
+        .instance(true)
+        .instance_named("mem.ttl", Duration::from_secs(5))
+        .inject::<Cache>()
+        .build()
+        .unwrap();
+    match inject.get::<Cache>().unwrap() {
+        Cache::Mem { ttl } => assert_eq!(ttl, Duration::from_secs(5)),
+        Cache::Disabled => panic!("unexpected variant"),
+    }
+}
+
+#[test]
+fn resolve_default_expressions() {
+    use std::time::Duration;
+
+    mod defaults {
+        pub fn port() -> u16 {
+            8080
+        }
+    }
+
+    #[derive(Clone)]
+    struct HttpConfig {
+        timeout_secs: u64,
+    }
+    #[derive(Clone, Debug, PartialEq)]
+    enum Level {
+        Info,
+    }
+    const DEFAULT_NAME: &str = "client";
+    #[derive(Component, Clone)]
+    struct HttpClient {
+        #[component(default = defaults::port)]
+        port: u16,
+        #[component(default = u16::MAX)]
+        max_port: u16,
+        #[component(default = Level::Info)]
+        level: Level,
+        #[component(default = DEFAULT_NAME)]
+        default_name: &'static str,
+        #[component(default = Vec::new)]
+        headers: Vec<String>,
+        #[component(default = 3)]
+        retries: u8,
+        #[component(default = Duration::from_millis(100))]
+        retry_delay: Duration,
+        #[component(default_with = |config: HttpConfig| Duration::from_secs(config.timeout_secs))]
+        timeout: Duration,
+        #[component(default_with = timeout_name)]
+        name: String,
+    }
+
+    fn timeout_name(config: HttpConfig, x: u32) -> String {
+        format!("{}-{}", config.timeout_secs, x)
+    }
+
+    let inject = InjectionBinder::new()
+        .instance(1u32)
+        .inject::<HttpClient>()
+        .instance(HttpConfig { timeout_secs: 5 })
+        .build()
+        .unwrap();
+
+    let client: HttpClient = inject.get().unwrap();
+    assert_eq!(client.port, 8080);
+    assert_eq!(client.max_port, u16::MAX);
+    assert_eq!(client.level, Level::Info);
+    assert_eq!(client.default_name, "client");
+    assert!(client.headers.is_empty());
+    assert_eq!(client.retries, 3);
+    assert_eq!(client.retry_delay, Duration::from_millis(100));
+    assert_eq!(client.timeout, Duration::from_secs(5));
+    assert_eq!(client.name, "5-1");
+
+    // arguments of default_with are dependencies checked by verify
+    let err = InjectionBinder::new()
+        .inject::<HttpClient>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("HttpConfig"));
+
+    // typed closure arguments are required by module! as well
+    let inject = mydi::module! {
+        instance u32 = 2;
+        instance HttpConfig = HttpConfig { timeout_secs: 7 };
+        inject HttpClient;
+    }
+    .build()
+    .unwrap();
+    let client: HttpClient = inject.get().unwrap();
+    assert_eq!(client.timeout, Duration::from_secs(7));
+    assert_eq!(client.name, "7-2");
+}
+
+#[test]
+fn default_if_missing_arguments() {
+    #[derive(Component, Clone, Default)]
+    struct HttpConfig {
+        port: u16,
+    }
+    #[derive(Clone, Debug, PartialEq)]
+    struct RetryPolicy(u8);
+    impl RetryPolicy {
+        fn no_retries() -> Self {
+            RetryPolicy(0)
+        }
+    }
+    #[derive(Component, Clone)]
+    struct HttpServer {
+        #[component(default_if_missing)]
+        config: HttpConfig,
+        #[component(default_if_missing = RetryPolicy::no_retries)]
+        retry_policy: RetryPolicy,
+        #[component(default_if_missing = 7)]
+        workers: u8,
+        #[component(default_if_missing = u64::MAX)]
+        max_body: u64,
+    }
+
+    // bound values are built before the component and injected
+    let inject = InjectionBinder::new()
+        .inject::<HttpServer>()
+        .inject::<HttpConfig>()
+        .instance(8080u16)
+        .instance(RetryPolicy(3))
+        .instance(2u8)
+        .build()
+        .unwrap();
+    let server: HttpServer = inject.get().unwrap();
+    assert_eq!(server.config.port, 8080);
+    assert_eq!(server.retry_policy, RetryPolicy(3));
+    assert_eq!(server.workers, 2);
+    assert_eq!(server.max_body, u64::MAX);
+
+    // missing values are not reported by verify
+    let inject = InjectionBinder::new()
+        .inject::<HttpServer>()
+        .build()
+        .unwrap();
+    let server: HttpServer = inject.get().unwrap();
+    assert_eq!(server.config.port, 0);
+    assert_eq!(server.retry_policy, RetryPolicy(0));
+    assert_eq!(server.workers, 7);
+    assert_eq!(server.max_body, u64::MAX);
+}
+
+#[test]
+fn provider_builds_new_values() {
+    use mydi::{Factory, FactoryFn, Provider};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Clone, Default)]
+    struct Counter(Arc<AtomicUsize>);
+    #[derive(Component, Clone)]
+    struct Worker {
+        #[component(default_with = |counter: Counter| counter.0.fetch_add(1, Ordering::SeqCst))]
+        id: usize,
+    }
+    #[derive(Clone)]
+    struct PartitionWorker {
+        partition: u32,
+        counter: Counter,
+    }
+    impl Factory<u32> for PartitionWorker {
+        type Dependencies = (Counter,);
+
+        fn create(partition: u32, (counter,): (Counter,)) -> Self {
+            Self { partition, counter }
+        }
+    }
+    #[derive(Component, Clone)]
+    struct Pool {
+        workers: Provider<Worker>,
+        partitions: FactoryFn<u32, PartitionWorker>,
+    }
+
+    let inject = InjectionBinder::new()
+        .inject::<Pool>()
+        .inject::<Provider<Worker>>()
+        .inject::<FactoryFn<u32, PartitionWorker>>()
+        .instance(Counter::default())
+        .build()
+        .unwrap();
+
+    let pool: Pool = inject.get().unwrap();
+    assert_eq!(pool.workers.get().unwrap().id, 0);
+    assert_eq!(pool.workers.get().unwrap().id, 1);
+    let worker = pool.partitions.call(7).unwrap();
+    assert_eq!(worker.partition, 7);
+    assert_eq!(worker.counter.0.load(Ordering::SeqCst), 2);
+
+    // providers and factories can be checked at compile time
+    let inject = mydi::module! {
+        instance Counter = Counter::default();
+        inject Pool;
+        inject Provider<Worker>;
+        inject FactoryFn<u32, PartitionWorker>;
+    }
+    .build()
+    .unwrap();
+    let pool: Pool = inject.get().unwrap();
+    assert_eq!(pool.partitions.call(1).unwrap().partition, 1);
+
+    // dependencies of provided types are verified, though the types are not bound
+    let err = InjectionBinder::new()
+        .inject::<Provider<Worker>>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Counter"));
+    let err = InjectionBinder::new()
+        .inject::<FactoryFn<u32, PartitionWorker>>()
+        .build()
+        .err()
+        .unwrap();
+    assert!(err.to_string().contains("Counter"));
+}